@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The function parameter names of some contracts, read from their Foundry artifacts
+/// (`<out>/<File>.sol/<Contract>.json`), as Medusa only prints the parameter types
+#[derive(Debug, Default)]
+pub struct AbiIndex {
+    /// (contract, function signature, parameter names)
+    functions: Vec<(String, String, Vec<String>)>,
+}
+
+impl AbiIndex {
+    /// Load the abi of the given contracts, skipping the ones without artifact
+    /// (the artifacts directory not existing is not an error, the project might not be built)
+    pub fn load(artifacts_dir: &Path, contracts: &[&str]) -> Result<AbiIndex> {
+        let mut functions = Vec::new();
+
+        for contract in contracts {
+            let Some(artifact) = find_artifact(artifacts_dir, contract)? else {
+                continue;
+            };
+
+            let content = fs::read_to_string(&artifact)
+                .with_context(|| format!("Failed to read {}", artifact.display()))?;
+            let parsed: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", artifact.display()))?;
+
+            functions.extend(
+                parse_abi(&parsed["abi"])
+                    .into_iter()
+                    .map(|(signature, names)| (contract.to_string(), signature, names)),
+            );
+        }
+
+        Ok(AbiIndex { functions })
+    }
+
+    /// The parameter names of a function, eg `prop_foo(uint256,bytes)`, if known
+    /// Unnamed parameters are empty strings
+    pub fn parameter_names(&self, contract: &str, signature: &str) -> Option<&[String]> {
+        self.functions
+            .iter()
+            .find(|(declaring, function, _)| declaring == contract && function == signature)
+            .map(|(_, _, names)| names.as_slice())
+    }
+}
+
+/// `<out>/<File>.sol/<Contract>.json`, None if there is none
+fn find_artifact(artifacts_dir: &Path, contract: &str) -> Result<Option<PathBuf>> {
+    if !artifacts_dir.is_dir() {
+        return Ok(None);
+    }
+
+    let mut directories: Vec<PathBuf> = fs::read_dir(artifacts_dir)
+        .with_context(|| format!("Failed to list {}", artifacts_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    directories.sort();
+
+    Ok(directories
+        .into_iter()
+        .map(|directory| directory.join(format!("{}.json", contract)))
+        .find(|artifact| artifact.is_file()))
+}
+
+/// The signature and parameter names of every function of an abi
+fn parse_abi(abi: &Value) -> Vec<(String, Vec<String>)> {
+    abi.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry["type"] == "function")
+        .filter_map(|entry| {
+            let name = entry["name"].as_str()?;
+            let inputs = entry["inputs"].as_array()?;
+
+            let types: Vec<String> = inputs.iter().map(canonical_type).collect();
+            let names = inputs
+                .iter()
+                .map(|input| input["name"].as_str().unwrap_or_default().to_string())
+                .collect();
+
+            Some((format!("{}({})", name, types.join(",")), names))
+        })
+        .collect()
+}
+
+/// The type as printed in a signature, tuples being expanded: `tuple[]` is `(uint256,bytes)[]`
+fn canonical_type(parameter: &Value) -> String {
+    let type_ = parameter["type"].as_str().unwrap_or_default();
+
+    match type_.strip_prefix("tuple") {
+        Some(array_suffix) => {
+            let components: Vec<String> = parameter["components"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(canonical_type)
+                .collect();
+
+            format!("({}){}", components.join(","), array_suffix)
+        }
+        None => type_.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_abi() {
+        let abi: Value = serde_json::from_str(
+            r#"[
+                {"type": "constructor", "inputs": []},
+                {"type": "function", "name": "prop_foo", "inputs": [
+                    {"name": "amount", "type": "uint256"},
+                    {"name": "orders", "type": "tuple[]", "components": [
+                        {"name": "id", "type": "uint256"},
+                        {"name": "data", "type": "bytes"}
+                    ]},
+                    {"name": "", "type": "address"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_abi(&abi),
+            vec![(
+                "prop_foo(uint256,(uint256,bytes)[],address)".to_string(),
+                vec!["amount".to_string(), "orders".to_string(), String::new()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_load_missing_artifacts() {
+        let index = AbiIndex::load(Path::new("does/not/exist"), &["FuzzTest"]).unwrap();
+
+        assert_eq!(index.parameter_names("FuzzTest", "prop_foo()"), None);
+    }
+}
@@ -0,0 +1,110 @@
+use youdusa::ast::Ast;
+use youdusa::sources::{to_slash, SourceIndex, SourceLocation};
+
+/// Build a GitHub Actions `::error` workflow command per reproducer, so that failures show
+/// inline in the PR diff
+/// The annotation is located at the property declaration in the harness sources, or else at
+/// the reproducer function in the generated contracts (`written`, empty if none were written)
+pub fn github_annotations(
+    reproducers: &[Ast],
+    sources: &SourceIndex,
+    written: &SourceIndex,
+) -> Vec<String> {
+    reproducers
+        .iter()
+        .filter_map(|ast| match ast {
+            Ast::FunctionDeclaration(fn_declaration) => Some(fn_declaration),
+            Ast::Statement(_) => None,
+        })
+        .map(|fn_declaration| {
+            let (contract, property) = match fn_declaration.failed_property() {
+                Some(failed_property) => (
+                    failed_property.contract.as_str(),
+                    failed_property.property.as_str(),
+                ),
+                None => ("", fn_declaration.name()),
+            };
+
+            let location = sources
+                .find_function(contract, property)
+                .or_else(|| written.find_function("", fn_declaration.name()));
+
+            error_command(
+                location.as_ref(),
+                &format!(
+                    "Property {} failed after {} calls",
+                    property,
+                    fn_declaration.calls().count()
+                ),
+            )
+        })
+        .collect()
+}
+
+/// `::error file=...,line=...::message`, without location if unknown
+fn error_command(location: Option<&SourceLocation>, message: &str) -> String {
+    match location {
+        Some(location) => format!(
+            "::error file={},line={}::{}",
+            escape_property(&to_slash(&location.path)),
+            location.line,
+            escape_data(message)
+        ),
+        None => format!("::error::{}", escape_data(message)),
+    }
+}
+
+/// Escape the workflow command message
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value, which also can't contain ':' or ','
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use youdusa::ast::FunctionDeclaration;
+    use youdusa::types::{FailedProperty, FailureKind};
+
+    #[test]
+    fn test_github_annotations_without_location() {
+        let reproducers = vec![Ast::FunctionDeclaration(
+            FunctionDeclaration::new("test_prop_a").with_failed_property(FailedProperty {
+                contract: "FuzzTest".to_string(),
+                property: "prop_a".to_string(),
+                kind: FailureKind::Assertion,
+            }),
+        )];
+
+        assert_eq!(
+            github_annotations(
+                &reproducers,
+                &SourceIndex::default(),
+                &SourceIndex::default()
+            ),
+            vec!["::error::Property prop_a failed after 0 calls"]
+        );
+    }
+
+    #[test]
+    fn test_error_command() {
+        let location = SourceLocation {
+            path: PathBuf::from("test/a,b.sol"),
+            line: 12,
+        };
+
+        assert_eq!(
+            error_command(Some(&location), "100% broken\n"),
+            "::error file=test/a%2Cb.sol,line=12::100%25 broken%0A"
+        );
+    }
+}
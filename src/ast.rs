@@ -1,22 +1,28 @@
+use crate::trace::TraceCall;
+use crate::types::{serialize_optional_u256_decimal, CallContext, FailedProperty, Provenance};
 use primitive_types::U256;
+use serde::Serialize;
 
 /// Youdusa AST, used to build representation of reproducers
 /// Only the relevant part of Solidity semantics is implemented
 /// Excluded (place-holdered if needed in the future): Expressions (function calls are represented as pure statement, as we don't get
 /// their returned values), Other types, Other statements
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Ast {
     FunctionDeclaration(FunctionDeclaration), // Fn declaration, this is the root
     Statement(Statement),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct FunctionDeclaration {
     name: String,
     visibility: Visibility,
     arguments: Vec<Argument>,
     return_type: Type,
     children: Vec<Ast>,
+    failed_property: Option<FailedProperty>,
+    provenance: Option<Provenance>,
+    trace: Vec<TraceCall>,
 }
 
 impl FunctionDeclaration {
@@ -28,9 +34,24 @@ impl FunctionDeclaration {
             arguments: Vec::new(),
             return_type: Type::None,
             children: Vec::new(),
+            failed_property: None,
+            provenance: None,
+            trace: Vec::new(),
         }
     }
 
+    /// Attach the property this function reproduces
+    pub fn with_failed_property(mut self, failed_property: FailedProperty) -> Self {
+        self.failed_property = Some(failed_property);
+        self
+    }
+
+    /// Attach where this function comes from in the Medusa log
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
     pub fn add_child(&mut self, child: Ast) {
         self.children.push(child);
     }
@@ -42,54 +63,89 @@ impl FunctionDeclaration {
     pub fn children(&self) -> &[Ast] {
         &self.children
     }
+
+    /// Take the children out, leaving none (see `set_children` to put them back, eg once rewritten)
+    pub fn take_children(&mut self) -> Vec<Ast> {
+        std::mem::take(&mut self.children)
+    }
+
+    pub fn set_children(&mut self, children: Vec<Ast>) {
+        self.children = children;
+    }
+
+    pub fn failed_property(&self) -> Option<&FailedProperty> {
+        self.failed_property.as_ref()
+    }
+
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// The Medusa execution trace following the call sequence, as a call tree (empty if none)
+    pub fn trace(&self) -> &[TraceCall] {
+        &self.trace
+    }
+
+    pub fn set_trace(&mut self, trace: Vec<TraceCall>) {
+        self.trace = trace;
+    }
+
+    /// The Medusa calls reproduced, in the sequence order
+    pub fn calls(&self) -> impl Iterator<Item = &CallContext> {
+        self.children.iter().filter_map(|child| match child {
+            Ast::Statement(Statement::ContractCall(FunctionCall {
+                context: Some(context),
+                ..
+            })) => Some(context.as_ref()),
+            _ => None,
+        })
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Visibility {
     Public,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Argument {
     name: String,
     type_: Type,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Type {
     None,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Statement {
     ContractCall(FunctionCall),
+    /// A call to the cheatcodes contract, its target (vm, hevm) is picked when emitting
+    CheatcodeCall(FunctionCall),
 }
 
 impl Statement {
     pub fn new_prank(pranked_address: &str) -> Self {
-        Self::ContractCall(FunctionCall {
-            target: Some("vm".to_string()),
-            function_name: "prank".to_string(),
-            value: None,
-            arguments: vec![pranked_address.to_string()],
-        })
+        Self::new_cheatcode_call("prank", vec![pranked_address.to_string()])
     }
 
     pub fn new_roll(block_to_roll: i32) -> Self {
-        Self::ContractCall(FunctionCall {
-            target: Some("vm".to_string()),
-            function_name: "roll".to_string(),
-            value: None,
-            arguments: vec![block_to_roll.to_string()],
-        })
+        Self::new_cheatcode_call("roll", vec![block_to_roll.to_string()])
     }
 
     pub fn new_warp(timestamp_to_warp_to: i32) -> Self {
-        Self::ContractCall(FunctionCall {
-            target: Some("vm".to_string()),
-            function_name: "warp".to_string(),
+        Self::new_cheatcode_call("warp", vec![timestamp_to_warp_to.to_string()])
+    }
+
+    pub fn new_cheatcode_call(function_name: &str, arguments: Vec<String>) -> Self {
+        Self::CheatcodeCall(FunctionCall {
+            target: None,
+            function_name: function_name.to_string(),
             value: None,
-            arguments: vec![timestamp_to_warp_to.to_string()],
+            arguments,
+            context: None,
+            medusa_line: None,
         })
     }
 
@@ -98,20 +154,32 @@ impl Statement {
         function_name: String,
         value: Option<U256>,
         arguments: Vec<String>,
+        context: Option<CallContext>,
     ) -> Self {
         Self::ContractCall(FunctionCall {
             target,
             function_name,
             value,
             arguments,
+            context: context.map(Box::new),
+            medusa_line: None,
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct FunctionCall {
     pub target: Option<String>,
     pub function_name: String,
+    #[serde(serialize_with = "serialize_optional_u256_decimal")]
     pub value: Option<U256>,
     pub arguments: Vec<String>,
+
+    /// The Medusa call this is reproducing, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Box<CallContext>>,
+
+    /// The Medusa call sequence line this call reproduces, as printed (`1) FuzzTest.prop_foo(uint256)(1) (block=...)`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medusa_line: Option<String>,
 }
@@ -0,0 +1,189 @@
+use crate::ast::Ast;
+use crate::backends::EmitterBackend;
+use crate::parser::split_top_level;
+use crate::types::{CallContext, TypedArgument};
+
+use anyhow::{Context, Result};
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+const SCRIPT_HEADER: &str = r#"#!/usr/bin/env bash
+# Replay the failing call sequences found by Medusa on an anvil node (generated by youdusa)
+# Usage: HARNESS=<deployed harness address> ./replay.sh [test_name...]
+set -euo pipefail
+
+RPC_URL="${RPC_URL:-http://127.0.0.1:8545}"
+HARNESS="${HARNESS:?Set HARNESS to the address of the deployed harness}"
+
+# Mine up to the block before $1 and set the next timestamp to $2, so that the next transaction
+# is included at (or as close as possible to) this block and timestamp
+advance() {
+    local current latest_time
+    current=$(cast block-number --rpc-url "$RPC_URL")
+    if [ "$1" -gt $((current + 1)) ]; then
+        cast rpc --rpc-url "$RPC_URL" anvil_mine $(($1 - current - 1)) > /dev/null
+    fi
+    latest_time=$(cast block latest --field timestamp --rpc-url "$RPC_URL")
+    if [ "$2" -gt "$latest_time" ]; then
+        cast rpc --rpc-url "$RPC_URL" anvil_setNextBlockTimestamp "$2" > /dev/null
+    fi
+}
+"#;
+
+/// Emit a bash script replaying the reproducers on an anvil node with cast, one function per
+/// reproducer (ran in order, or the ones passed as arguments)
+/// Each call is preceded by the same roll/warp/prank as in the Solidity reproducers
+pub struct CastBackend;
+
+impl CastBackend {
+    /// The cast/anvil commands for a single call
+    /// A call whose signature couldn't be parsed can't be abi encoded: the replay stops there, failing
+    fn call_commands(call: &CallContext) -> Result<String> {
+        let mut commands = String::new();
+        let sender = &call.cheats.caller_to_prank;
+
+        let Some(signature) = &call.signature else {
+            writeln!(
+                commands,
+                "    echo {} >&2",
+                shell_quote(&format!(
+                    "Cannot replay {}.{}: its signature could not be parsed",
+                    call.contract,
+                    call.format_call()
+                ))
+            )?;
+            writeln!(commands, "    return 1")?;
+
+            return Ok(commands);
+        };
+
+        writeln!(
+            commands,
+            "    advance {} {}",
+            call.cheats.block_to_roll, call.cheats.timestamp_to_warp_to
+        )?;
+        writeln!(
+            commands,
+            "    cast rpc --rpc-url \"$RPC_URL\" anvil_impersonateAccount {} > /dev/null",
+            sender
+        )?;
+
+        write!(
+            commands,
+            "    cast send --rpc-url \"$RPC_URL\" --unlocked --from {}",
+            sender
+        )?;
+        if !call.cheats.value.is_zero() {
+            write!(commands, " --value {}", call.cheats.value)?;
+        }
+        write!(commands, " \"$HARNESS\" {}", shell_quote(signature))?;
+        for argument in &call.arguments {
+            write!(commands, " {}", shell_quote(&cast_argument(argument)))?;
+        }
+        writeln!(commands)?;
+
+        Ok(commands)
+    }
+}
+
+impl EmitterBackend for CastBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let mut script = String::from(SCRIPT_HEADER);
+        let mut test_names = Vec::new();
+
+        for ast in reproducers {
+            let Ast::FunctionDeclaration(fn_declaration) = ast else {
+                continue;
+            };
+
+            script.push('\n');
+            if let Some(failed_property) = fn_declaration.failed_property() {
+                writeln!(
+                    script,
+                    "# {}.{} ({})",
+                    failed_property.contract, failed_property.property, failed_property.kind
+                )?;
+            }
+            writeln!(script, "replay_{}() {{", fn_declaration.name())?;
+            for call in fn_declaration.calls() {
+                script.push_str(&Self::call_commands(call)?);
+            }
+            writeln!(script, "}}")?;
+
+            test_names.push(fn_declaration.name());
+        }
+
+        writeln!(script, "\nif [ $# -eq 0 ]; then")?;
+        writeln!(script, "    set -- {}", test_names.join(" "))?;
+        writeln!(script, "fi\n")?;
+        writeln!(script, "for test in \"$@\"; do")?;
+        writeln!(script, "    echo \"Replaying $test\"")?;
+        writeln!(script, "    \"replay_$test\"")?;
+        writeln!(script, "done")?;
+
+        write!(writer, "{}", script).context("Error: Failed to write the replay script")?;
+
+        Ok(())
+    }
+}
+
+/// The argument as expected by cast: empty bytes are `0x`, including in tuples
+fn cast_argument(argument: &TypedArgument) -> String {
+    let TypedArgument { type_, value } = argument;
+
+    if type_.starts_with('(') && value.starts_with('(') {
+        let types = split_top_level(&type_[1..type_.len() - 1]);
+        let values = split_top_level(&value[1..value.len() - 1]);
+
+        let components: Vec<String> = types
+            .into_iter()
+            .enumerate()
+            .map(|(i, type_)| {
+                cast_argument(&TypedArgument {
+                    type_,
+                    value: values.get(i).cloned().unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        return format!("({})", components.join(","));
+    }
+
+    if value.is_empty() && type_.starts_with("bytes") {
+        return "0x".to_string();
+    }
+
+    value.clone()
+}
+
+/// Single quote a shell word
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(type_: &str, value: &str) -> TypedArgument {
+        TypedArgument {
+            type_: type_.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cast_argument() {
+        assert_eq!(cast_argument(&argument("uint256", "123")), "123");
+        assert_eq!(cast_argument(&argument("bytes", "")), "0x");
+        assert_eq!(
+            cast_argument(&argument("(uint256,(bytes,address))", "(123, (, 0x12))")),
+            "(123,(0x,0x12))"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -0,0 +1,171 @@
+use crate::ast::{Ast, FunctionDeclaration};
+use crate::backends::EmitterBackend;
+use crate::emitter::{Emitter, EmitterOptions};
+
+use anyhow::{Context, Result};
+use askama::Template;
+use std::io::Write;
+
+/// Emit a self-contained HTML report (no external assets), with summary statistics, a filter
+/// box and, per failure, its collapsible call sequence and a copyable Foundry reproducer
+pub struct HtmlBackend {
+    /// Used to emit the Solidity reproducers included in the report
+    options: EmitterOptions,
+}
+
+#[derive(Template)]
+#[template(path = "report.html")]
+struct HtmlReport {
+    youdusa_version: &'static str,
+    property_count: usize,
+    call_count: usize,
+    shortest_sequence: usize,
+    /// Number of failures per failure kind
+    kinds: Vec<(String, usize)>,
+    failures: Vec<HtmlFailure>,
+}
+
+struct HtmlFailure {
+    title: String,
+    kind: String,
+    calls: Vec<HtmlCall>,
+    reproducer: String,
+    /// Lowercased text the filter box matches against
+    search: String,
+}
+
+struct HtmlCall {
+    step: usize,
+    sender: String,
+    block: i32,
+    time: i32,
+    value: String,
+    call: String,
+}
+
+impl HtmlBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    fn failure(&self, ast: &Ast, fn_declaration: &FunctionDeclaration) -> Result<HtmlFailure> {
+        let (title, kind) = match fn_declaration.failed_property() {
+            Some(failed_property) => (
+                format!(
+                    "{}.{} ({})",
+                    failed_property.contract,
+                    failed_property.property,
+                    fn_declaration.name()
+                ),
+                failed_property.kind.to_string(),
+            ),
+            None => (fn_declaration.name().to_string(), "unknown".to_string()),
+        };
+
+        let calls: Vec<HtmlCall> = fn_declaration
+            .calls()
+            .enumerate()
+            .map(|(step, call)| HtmlCall {
+                step: step + 1,
+                sender: call.cheats.caller_to_prank.clone(),
+                block: call.cheats.block_to_roll,
+                time: call.cheats.timestamp_to_warp_to,
+                value: call.cheats.value.to_string(),
+                call: call.format_call(),
+            })
+            .collect();
+
+        let mut emitter = Emitter::new().with_options(self.options.clone());
+        emitter.emit(ast)?;
+
+        let mut search = format!("{} {}", title, kind);
+        for call in &calls {
+            search.push(' ');
+            search.push_str(&call.sender);
+            search.push(' ');
+            search.push_str(&call.call);
+        }
+
+        Ok(HtmlFailure {
+            title,
+            kind,
+            calls,
+            reproducer: emitter.get_emitted().trim_end().to_string(),
+            search: search.to_lowercase(),
+        })
+    }
+}
+
+impl EmitterBackend for HtmlBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let declarations: Vec<(&Ast, &FunctionDeclaration)> = reproducers
+            .iter()
+            .filter_map(|ast| match ast {
+                Ast::FunctionDeclaration(fn_declaration) => Some((ast, fn_declaration)),
+                Ast::Statement(_) => None,
+            })
+            .collect();
+
+        let mut properties: Vec<&str> = declarations
+            .iter()
+            .map(
+                |(_, fn_declaration)| match fn_declaration.failed_property() {
+                    Some(failed_property) => failed_property.property.as_str(),
+                    None => fn_declaration.name(),
+                },
+            )
+            .collect();
+        properties.sort_unstable();
+        properties.dedup();
+
+        let failures = declarations
+            .iter()
+            .map(|(ast, fn_declaration)| self.failure(ast, fn_declaration))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut kinds: Vec<(String, usize)> = Vec::new();
+        for failure in &failures {
+            match kinds.iter_mut().find(|(kind, _)| *kind == failure.kind) {
+                Some((_, count)) => *count += 1,
+                None => kinds.push((failure.kind.clone(), 1)),
+            }
+        }
+
+        let report = HtmlReport {
+            youdusa_version: env!("CARGO_PKG_VERSION"),
+            property_count: properties.len(),
+            call_count: failures.iter().map(|failure| failure.calls.len()).sum(),
+            shortest_sequence: failures
+                .iter()
+                .map(|failure| failure.calls.len())
+                .min()
+                .unwrap_or_default(),
+            kinds,
+            failures,
+        };
+
+        let rendered = report
+            .render()
+            .context("Failed to render the HTML report")?;
+        writeln!(writer, "{}", rendered).context("Error: Failed to write the report")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_no_failure() {
+        let mut backend = HtmlBackend::new(EmitterOptions::default());
+        let mut output = Vec::new();
+
+        backend.emit_reproducers(&[], &mut output).unwrap();
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("<p class=\"empty\">No failing property found.</p>"));
+        assert!(!output_str.contains("<input id=\"filter\""));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::ast::Ast;
+use crate::backends::EmitterBackend;
+use crate::trace::TraceCall;
+use crate::types::{CallContext, FailedProperty};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Write;
+
+/// Version of the json document, bumped on every breaking change of its layout
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+/// Emit the reproducers as a single json document, for dashboards and scripts
+pub struct JsonBackend;
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    version: u32,
+    youdusa_version: &'static str,
+    failures: Vec<JsonFailure<'a>>,
+}
+
+/// One failing property, with the call sequence breaking it
+#[derive(Serialize)]
+struct JsonFailure<'a> {
+    test_name: &'a str,
+    #[serde(flatten)]
+    failed_property: Option<&'a FailedProperty>,
+    calls: Vec<&'a CallContext>,
+
+    /// The execution trace, as a call tree (omitted if Medusa didn't print one)
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    trace: &'a [TraceCall],
+}
+
+impl EmitterBackend for JsonBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let failures = reproducers
+            .iter()
+            .filter_map(|ast| match ast {
+                Ast::FunctionDeclaration(fn_declaration) => Some(JsonFailure {
+                    test_name: fn_declaration.name(),
+                    failed_property: fn_declaration.failed_property(),
+                    calls: fn_declaration.calls().collect(),
+                    trace: fn_declaration.trace(),
+                }),
+                Ast::Statement(_) => None,
+            })
+            .collect();
+
+        let report = JsonReport {
+            version: JSON_FORMAT_VERSION,
+            youdusa_version: env!("CARGO_PKG_VERSION"),
+            failures,
+        };
+
+        serde_json::to_writer_pretty(&mut *writer, &report)
+            .context("Error: Failed to serialize the reproducers")?;
+        writeln!(writer).context("Error: Failed to write reproducer")?;
+
+        Ok(())
+    }
+}
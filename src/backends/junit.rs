@@ -0,0 +1,140 @@
+use crate::ast::Ast;
+use crate::backends::{escape_xml, group_by_property, EmitterBackend, PropertyFailures};
+use crate::emitter::{Emitter, EmitterOptions};
+
+use anyhow::{Context, Result};
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+/// Emit a JUnit XML report, one testsuite per contract and one failed testcase per property
+pub struct JunitBackend {
+    /// Used to emit the Solidity reproducers included in the failures
+    options: EmitterOptions,
+}
+
+impl JunitBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    /// The testcase of a failing property, its failure being the call sequences and reproducers
+    fn testcase(&self, failures: &PropertyFailures) -> Result<String> {
+        let mut testcase = String::new();
+        let mut details = String::new();
+
+        for (ast, fn_declaration) in &failures.reproducers {
+            writeln!(details, "{}:", fn_declaration.name())?;
+            for (step, call) in fn_declaration.calls().enumerate() {
+                writeln!(
+                    details,
+                    "  {}. {} (sender={}, block={}, time={}, value={})",
+                    step + 1,
+                    call.format_call(),
+                    call.cheats.caller_to_prank,
+                    call.cheats.block_to_roll,
+                    call.cheats.timestamp_to_warp_to,
+                    call.cheats.value
+                )?;
+            }
+
+            let mut emitter = Emitter::new().with_options(self.options.clone());
+            emitter.emit(ast)?;
+            writeln!(details, "\n{}\n", emitter.get_emitted().trim_end())?;
+        }
+
+        let shortest = failures
+            .reproducers
+            .iter()
+            .map(|(_, fn_declaration)| fn_declaration.calls().count())
+            .min()
+            .unwrap_or_default();
+        let kind = failures
+            .failed_property
+            .map(|failed_property| failed_property.kind.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        writeln!(
+            testcase,
+            "    <testcase name=\"{}\" classname=\"{}\">",
+            escape_xml(failures.property()),
+            escape_xml(failures.contract().unwrap_or("medusa"))
+        )?;
+        writeln!(
+            testcase,
+            "      <failure type=\"{}\" message=\"Property {} failed after {} calls\">{}</failure>",
+            kind,
+            escape_xml(failures.property()),
+            shortest,
+            escape_xml(details.trim_end())
+        )?;
+        writeln!(testcase, "    </testcase>")?;
+
+        Ok(testcase)
+    }
+}
+
+impl EmitterBackend for JunitBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let failures = group_by_property(reproducers);
+
+        // One testsuite per contract, in order of first failure
+        let mut contracts: Vec<&str> = Vec::new();
+        for failure in &failures {
+            let contract = failure.contract().unwrap_or("medusa");
+            if !contracts.contains(&contract) {
+                contracts.push(contract);
+            }
+        }
+
+        let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(
+            report,
+            "<testsuites name=\"youdusa\" tests=\"{0}\" failures=\"{0}\">",
+            failures.len()
+        )?;
+
+        for contract in contracts {
+            let testcases: Vec<&PropertyFailures> = failures
+                .iter()
+                .filter(|failure| failure.contract().unwrap_or("medusa") == contract)
+                .collect();
+
+            writeln!(
+                report,
+                "  <testsuite name=\"{}\" tests=\"{1}\" failures=\"{1}\">",
+                escape_xml(contract),
+                testcases.len()
+            )?;
+            for testcase in testcases {
+                report.push_str(&self.testcase(testcase)?);
+            }
+            writeln!(report, "  </testsuite>")?;
+        }
+
+        writeln!(report, "</testsuites>")?;
+
+        write!(writer, "{}", report).context("Error: Failed to write the report")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_no_failure() {
+        let mut backend = JunitBackend::new(EmitterOptions::default());
+        let mut output = Vec::new();
+
+        backend.emit_reproducers(&[], &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuites name=\"youdusa\" tests=\"0\" failures=\"0\">\n\
+             </testsuites>\n"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::ast::{Ast, FunctionDeclaration};
+use crate::backends::EmitterBackend;
+use crate::emitter::{Emitter, EmitterOptions};
+use crate::trace;
+
+use anyhow::{Context, Result};
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+/// Emit a Markdown report of the failures, suitable for a PR comment
+pub struct MarkdownBackend {
+    /// Used to emit the Solidity reproducers included in the report
+    options: EmitterOptions,
+}
+
+impl MarkdownBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    /// The section of a single failing property: summary, call sequence and reproducer
+    fn failure_section(&self, ast: &Ast, fn_declaration: &FunctionDeclaration) -> Result<String> {
+        let mut section = String::new();
+        let calls: Vec<_> = fn_declaration.calls().collect();
+
+        match fn_declaration.failed_property() {
+            Some(failed_property) => {
+                writeln!(
+                    section,
+                    "### `{}.{}`\n",
+                    failed_property.contract, failed_property.property
+                )?;
+                writeln!(section, "- **Failure:** {}", failed_property.kind)?;
+            }
+            None => writeln!(section, "### `{}`\n", fn_declaration.name())?,
+        }
+        writeln!(section, "- **Sequence length:** {} calls", calls.len())?;
+        writeln!(section, "- **Reproducer:** `{}`\n", fn_declaration.name())?;
+
+        writeln!(section, "| Step | Sender | Block | Time | Value | Call |")?;
+        writeln!(section, "| --- | --- | --- | --- | --- | --- |")?;
+        for (step, call) in calls.iter().enumerate() {
+            writeln!(
+                section,
+                "| {} | `{}` | {} | {} | {} | `{}` |",
+                step + 1,
+                call.cheats.caller_to_prank,
+                call.cheats.block_to_roll,
+                call.cheats.timestamp_to_warp_to,
+                call.cheats.value,
+                escape_cell(&call.format_call())
+            )?;
+        }
+
+        if !fn_declaration.trace().is_empty() {
+            writeln!(section, "\n<details>\n<summary>Execution trace</summary>\n")?;
+            writeln!(
+                section,
+                "```\n{}```\n",
+                trace::render(fn_declaration.trace())
+            )?;
+            writeln!(section, "</details>")?;
+        }
+
+        let mut emitter = Emitter::new().with_options(self.options.clone());
+        emitter.emit(ast)?;
+
+        writeln!(
+            section,
+            "\n<details>\n<summary>Foundry reproducer</summary>\n"
+        )?;
+        writeln!(
+            section,
+            "```solidity\n{}\n```\n",
+            emitter.get_emitted().trim_end()
+        )?;
+        writeln!(section, "</details>")?;
+
+        Ok(section)
+    }
+}
+
+impl EmitterBackend for MarkdownBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let failures: Vec<(&Ast, &FunctionDeclaration)> = reproducers
+            .iter()
+            .filter_map(|ast| match ast {
+                Ast::FunctionDeclaration(fn_declaration) => Some((ast, fn_declaration)),
+                Ast::Statement(_) => None,
+            })
+            .collect();
+
+        let mut properties: Vec<&str> = failures
+            .iter()
+            .filter_map(|(_, failure)| failure.failed_property())
+            .map(|failed_property| failed_property.property.as_str())
+            .collect();
+        properties.sort_unstable();
+        properties.dedup();
+
+        let total_calls: usize = failures
+            .iter()
+            .map(|(_, failure)| failure.calls().count())
+            .sum();
+
+        let mut report = String::from("## Youdusa fuzzing report\n\n");
+
+        if failures.is_empty() {
+            report.push_str("No failing property found.\n");
+        } else {
+            writeln!(
+                report,
+                "**{} failure(s)** on **{} distinct property(ies)**, {} calls in total.\n",
+                failures.len(),
+                properties.len(),
+                total_calls
+            )?;
+
+            for (ast, failure) in failures {
+                report.push_str(&self.failure_section(ast, failure)?);
+                report.push('\n');
+            }
+        }
+
+        write!(writer, "{}", report).context("Error: Failed to write the report")?;
+
+        Ok(())
+    }
+}
+
+/// Make a value fit in a code span of a table cell: the pipes (ending the cell) are escaped,
+/// the backticks (ending the code span) replaced with quotes and the line breaks (ending the row) with spaces
+fn escape_cell(content: &str) -> String {
+    content
+        .replace('|', "\\|")
+        .replace('`', "'")
+        .replace("\r\n", " ")
+        .replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_no_failure() {
+        let mut backend = MarkdownBackend::new(EmitterOptions::default());
+        let mut output = Vec::new();
+
+        backend.emit_reproducers(&[], &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "## Youdusa fuzzing report\n\nNo failing property found.\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("foo(a|b)"), "foo(a\\|b)");
+        assert_eq!(escape_cell("foo(\"`a`\")"), "foo(\"'a'\")");
+        assert_eq!(escape_cell("foo(\"a\r\nb\nc\")"), "foo(\"a b c\")");
+    }
+}
@@ -0,0 +1,184 @@
+use crate::ast::Ast;
+use crate::backends::EmitterBackend;
+use crate::types::CallContext;
+
+use anyhow::{Context, Result};
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+/// Emit a Mermaid `sequenceDiagram` per reproducer, in Markdown code blocks (as rendered by GitHub)
+/// Senders are actors, the called contracts participants, and block/time jumps notes
+pub struct MermaidBackend;
+
+impl MermaidBackend {
+    /// The diagram of a single call sequence
+    fn diagram(calls: &[&CallContext]) -> Result<String> {
+        let mut diagram = String::from("sequenceDiagram\n");
+
+        let mut senders: Vec<&str> = Vec::new();
+        let mut contracts: Vec<&str> = Vec::new();
+        for call in calls {
+            if !senders.contains(&call.cheats.caller_to_prank.as_str()) {
+                senders.push(&call.cheats.caller_to_prank);
+            }
+            if !contracts.contains(&call.contract.as_str()) {
+                contracts.push(&call.contract);
+            }
+        }
+
+        for (index, sender) in senders.iter().enumerate() {
+            writeln!(diagram, "    actor S{} as {}", index + 1, sender)?;
+        }
+        for contract in &contracts {
+            writeln!(diagram, "    participant {}", contract)?;
+        }
+
+        let mut previous: Option<&CallContext> = None;
+        for call in calls {
+            let sender = senders
+                .iter()
+                .position(|sender| *sender == call.cheats.caller_to_prank)
+                .map(|index| index + 1)
+                .unwrap_or_default();
+
+            if let Some(note) = jump_note(previous, call) {
+                writeln!(
+                    diagram,
+                    "    Note over S{},{}: {}",
+                    sender, call.contract, note
+                )?;
+            }
+
+            let mut message = call.format_call();
+            if !call.cheats.value.is_zero() {
+                write!(message, " value: {}", call.cheats.value)?;
+            }
+            writeln!(
+                diagram,
+                "    S{}->>{}: {}",
+                sender,
+                call.contract,
+                escape_message(&message)
+            )?;
+
+            previous = Some(call);
+        }
+
+        Ok(diagram)
+    }
+}
+
+impl EmitterBackend for MermaidBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let mut output = String::new();
+
+        for ast in reproducers {
+            let Ast::FunctionDeclaration(fn_declaration) = ast else {
+                continue;
+            };
+
+            match fn_declaration.failed_property() {
+                Some(failed_property) => writeln!(
+                    output,
+                    "### `{}` ({}.{})\n",
+                    fn_declaration.name(),
+                    failed_property.contract,
+                    failed_property.property
+                )?,
+                None => writeln!(output, "### `{}`\n", fn_declaration.name())?,
+            }
+
+            let calls: Vec<&CallContext> = fn_declaration.calls().collect();
+            writeln!(output, "```mermaid\n{}```\n", Self::diagram(&calls)?)?;
+        }
+
+        write!(writer, "{}", output).context("Error: Failed to write the diagrams")?;
+
+        Ok(())
+    }
+}
+
+/// The block/time jump before a call (the absolute values for the first one), None if unchanged
+fn jump_note(previous: Option<&CallContext>, call: &CallContext) -> Option<String> {
+    let (block, time) = (call.cheats.block_to_roll, call.cheats.timestamp_to_warp_to);
+
+    let Some(previous) = previous else {
+        return Some(format!("block {}, time {}", block, time));
+    };
+
+    let mut jumps = Vec::new();
+    if block != previous.cheats.block_to_roll {
+        jumps.push(format!(
+            "roll {:+} blocks",
+            block - previous.cheats.block_to_roll
+        ));
+    }
+    if time != previous.cheats.timestamp_to_warp_to {
+        jumps.push(format!(
+            "warp {:+}s",
+            time - previous.cheats.timestamp_to_warp_to
+        ));
+    }
+
+    (!jumps.is_empty()).then(|| jumps.join(", "))
+}
+
+/// Semicolons end a Mermaid statement and '#' starts an entity code
+fn escape_message(message: &str) -> String {
+    message
+        .chars()
+        .map(|c| match c {
+            '#' => "#35;".to_string(),
+            ';' => "#59;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CheatsData;
+    use primitive_types::U256;
+
+    fn call(function: &str, block: i32, time: i32, sender: &str) -> CallContext {
+        CallContext {
+            contract: "FuzzTest".to_string(),
+            function: function.to_string(),
+            signature: Some(format!("{}()", function)),
+            arguments: Vec::new(),
+            raw_arguments: String::new(),
+            cheats: CheatsData {
+                block_to_roll: block,
+                timestamp_to_warp_to: time,
+                caller_to_prank: sender.to_string(),
+                value: U256::zero(),
+                gas: None,
+                gas_price: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_diagram() {
+        let first = call("prop_a", 1, 10, "0x01");
+        let second = call("prop_b", 1, 70, "0x02");
+
+        assert_eq!(
+            MermaidBackend::diagram(&[&first, &second]).unwrap(),
+            "sequenceDiagram\n    \
+             actor S1 as 0x01\n    \
+             actor S2 as 0x02\n    \
+             participant FuzzTest\n    \
+             Note over S1,FuzzTest: block 1, time 10\n    \
+             S1->>FuzzTest: prop_a()\n    \
+             Note over S2,FuzzTest: warp +60s\n    \
+             S2->>FuzzTest: prop_b()\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_message() {
+        assert_eq!(escape_message("a;b#c"), "a#59;b#35;c");
+    }
+}
@@ -0,0 +1,233 @@
+mod cast;
+mod html;
+mod json;
+mod junit;
+mod markdown;
+mod mermaid;
+mod narration;
+mod sarif;
+mod script;
+
+use crate::ast::{Ast, FunctionDeclaration};
+use crate::emitter::{Emitter, EmitterOptions};
+use crate::types::FailedProperty;
+
+pub use cast::CastBackend;
+pub use html::HtmlBackend;
+pub use json::{JsonBackend, JSON_FORMAT_VERSION};
+pub use junit::JunitBackend;
+pub use markdown::MarkdownBackend;
+pub use mermaid::MermaidBackend;
+pub use narration::NarrationBackend;
+pub use sarif::SarifBackend;
+pub use script::ScriptBackend;
+
+use anyhow::Result;
+use std::io::Write;
+
+/// An output format for the reproducers, built from their ast
+/// New formats are added by implementing this trait and registering them in BACKENDS
+pub trait EmitterBackend {
+    /// Write the output for all the reproducers found
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()>;
+}
+
+/// A backend selectable with `--format`
+pub struct BackendEntry {
+    /// The name used to select it
+    pub name: &'static str,
+
+    pub description: &'static str,
+
+    constructor: fn(&EmitterOptions) -> Box<dyn EmitterBackend>,
+}
+
+/// All the available backends, the first one being the default
+pub const BACKENDS: &[BackendEntry] = &[
+    BackendEntry {
+        name: "solidity",
+        description: "Foundry (or Chimera, see --profile) reproducer functions",
+        constructor: |options| Box::new(Emitter::new().with_options(options.clone())),
+    },
+    BackendEntry {
+        name: "json",
+        description: "A versioned json document, one entry per failing property",
+        constructor: |_| Box::new(JsonBackend),
+    },
+    BackendEntry {
+        name: "markdown",
+        description: "A Markdown report, with the call sequences and reproducers",
+        constructor: |options| Box::new(MarkdownBackend::new(options.clone())),
+    },
+    BackendEntry {
+        name: "junit",
+        description: "A JUnit XML report, one failed testcase per property",
+        constructor: |options| Box::new(JunitBackend::new(options.clone())),
+    },
+    BackendEntry {
+        name: "sarif",
+        description: "A SARIF log, one result per failing property",
+        constructor: |options| Box::new(SarifBackend::new(options.clone())),
+    },
+    BackendEntry {
+        name: "cast",
+        description: "A bash script replaying the sequences on an anvil node with cast",
+        constructor: |_| Box::new(CastBackend),
+    },
+    BackendEntry {
+        name: "script",
+        description: "A forge-std Script broadcasting the sequences to deployed harnesses",
+        constructor: |options| Box::new(ScriptBackend::new(options.clone())),
+    },
+    BackendEntry {
+        name: "mermaid",
+        description: "A Mermaid sequence diagram per reproducer, in Markdown code blocks",
+        constructor: |_| Box::new(MermaidBackend),
+    },
+    BackendEntry {
+        name: "html",
+        description: "A self-contained HTML report, with the call sequences and reproducers",
+        constructor: |options| Box::new(HtmlBackend::new(options.clone())),
+    },
+    BackendEntry {
+        name: "narration",
+        description: "A plain-English narration of the sequences, for bug reports",
+        constructor: |options| Box::new(NarrationBackend::new(options.clone())),
+    },
+];
+
+/// The name of the default backend
+pub fn default_backend_name() -> &'static str {
+    BACKENDS[0].name
+}
+
+/// The names of every available backend
+pub fn backend_names() -> Vec<&'static str> {
+    BACKENDS.iter().map(|backend| backend.name).collect()
+}
+
+/// Build the backend registered under a given name, None if there is none
+pub fn backend(name: &str, options: &EmitterOptions) -> Option<Box<dyn EmitterBackend>> {
+    BACKENDS
+        .iter()
+        .find(|backend| backend.name == name)
+        .map(|backend| (backend.constructor)(options))
+}
+
+/// The reproducers breaking a same property
+pub(crate) struct PropertyFailures<'a> {
+    /// None if the property couldn't be parsed, the failures are then grouped by test name
+    pub failed_property: Option<&'a FailedProperty>,
+
+    pub reproducers: Vec<(&'a Ast, &'a FunctionDeclaration)>,
+}
+
+impl PropertyFailures<'_> {
+    /// The name of the property, or the test name if unknown
+    pub fn property(&self) -> &str {
+        match self.failed_property {
+            Some(failed_property) => &failed_property.property,
+            None => self.reproducers[0].1.name(),
+        }
+    }
+
+    /// The contract declaring the property, if known
+    pub fn contract(&self) -> Option<&str> {
+        self.failed_property
+            .map(|failed_property| failed_property.contract.as_str())
+    }
+}
+
+/// Group the reproducers by failing property, in order of first failure
+pub(crate) fn group_by_property(reproducers: &[Ast]) -> Vec<PropertyFailures<'_>> {
+    let mut groups: Vec<PropertyFailures> = Vec::new();
+
+    for ast in reproducers {
+        let Ast::FunctionDeclaration(fn_declaration) = ast else {
+            continue;
+        };
+
+        let failed_property = fn_declaration.failed_property();
+        let group =
+            groups
+                .iter_mut()
+                .find(|group| match (group.failed_property, failed_property) {
+                    (Some(a), Some(b)) => a.contract == b.contract && a.property == b.property,
+                    _ => false,
+                });
+
+        match group {
+            Some(group) => group.reproducers.push((ast, fn_declaration)),
+            None => groups.push(PropertyFailures {
+                failed_property,
+                reproducers: vec![(ast, fn_declaration)],
+            }),
+        }
+    }
+
+    groups
+}
+
+/// Escape the xml special characters, in text and attribute values
+pub(crate) fn escape_xml(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_unknown() {
+        assert!(backend("foo", &EmitterOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_default_backend_is_registered() {
+        assert!(backend(default_backend_name(), &EmitterOptions::default()).is_some());
+        assert!(backend_names().contains(&"solidity"));
+    }
+
+    #[test]
+    fn test_group_by_property() {
+        let failing = |name: &str, property: &str| {
+            Ast::FunctionDeclaration(FunctionDeclaration::new(name).with_failed_property(
+                FailedProperty {
+                    contract: "FuzzTest".to_string(),
+                    property: property.to_string(),
+                    kind: crate::types::FailureKind::Assertion,
+                },
+            ))
+        };
+        let reproducers = vec![
+            failing("test_prop_a", "prop_a"),
+            failing("test_prop_b", "prop_b"),
+            failing("test_prop_a2", "prop_a"),
+            Ast::FunctionDeclaration(FunctionDeclaration::new("test_unknown")),
+        ];
+
+        let groups = group_by_property(&reproducers);
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| (group.property(), group.reproducers.len()))
+                .collect::<Vec<_>>(),
+            vec![("prop_a", 2), ("prop_b", 1), ("test_unknown", 1)]
+        );
+        assert_eq!(groups[2].contract(), None);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a<b & \"c\">'d'"),
+            "a&lt;b &amp; &quot;c&quot;&gt;&apos;d&apos;"
+        );
+    }
+}
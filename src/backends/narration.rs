@@ -0,0 +1,295 @@
+use crate::abi::AbiIndex;
+use crate::ast::Ast;
+use crate::backends::EmitterBackend;
+use crate::emitter::EmitterOptions;
+use crate::types::CallContext;
+
+use anyhow::{Context, Result};
+use primitive_types::U256;
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+
+/// Emit a plain-English narration of each failing sequence, as numbered sentences for bug reports:
+/// "Then, 3 days later, Bob calls withdraw(amount: 100), sending 1.5 ETH."
+/// Senders are named after their alias, if any, and arguments after the abi, when available
+pub struct NarrationBackend {
+    options: EmitterOptions,
+}
+
+impl NarrationBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    /// The sentence describing a call, `previous` being the call before it, if any
+    fn sentence(
+        &self,
+        call: &CallContext,
+        previous: Option<&CallContext>,
+        abi: &AbiIndex,
+    ) -> Result<String> {
+        let mut sentence = match previous {
+            None => format!(
+                "At block {} (timestamp {}), ",
+                call.cheats.block_to_roll, call.cheats.timestamp_to_warp_to
+            ),
+            Some(previous) => {
+                let elapsed = i64::from(call.cheats.timestamp_to_warp_to)
+                    - i64::from(previous.cheats.timestamp_to_warp_to);
+                let blocks = call.cheats.block_to_roll - previous.cheats.block_to_roll;
+
+                match (elapsed, blocks) {
+                    (0, 0) => "In the same block, ".to_string(),
+                    (0, blocks) => format!("Then, {} later, ", plural(blocks.into(), "block")),
+                    (elapsed, 0) => format!("Then, {} later, ", human_duration(elapsed)),
+                    (elapsed, blocks) => format!(
+                        "Then, {} later ({}), ",
+                        human_duration(elapsed),
+                        plural(blocks.into(), "block")
+                    ),
+                }
+            }
+        };
+
+        let arguments = match &call.signature {
+            Some(signature) => {
+                let names = abi.parameter_names(&call.contract, signature);
+                let arguments: Vec<String> = call
+                    .arguments
+                    .iter()
+                    .enumerate()
+                    .map(|(index, argument)| {
+                        let value = match argument.value.as_str() {
+                            "" => "\"\"",
+                            value => value,
+                        };
+
+                        match names.and_then(|names| names.get(index)) {
+                            Some(name) if !name.is_empty() => format!("{}: {}", name, value),
+                            _ => value.to_string(),
+                        }
+                    })
+                    .collect();
+
+                arguments.join(", ")
+            }
+            // Without signature, neither the types nor the names of the arguments are known
+            None => call.raw_arguments.clone(),
+        };
+
+        write!(
+            sentence,
+            "{} calls `{}({})`",
+            self.sender_name(&call.cheats.caller_to_prank),
+            call.function,
+            arguments
+        )?;
+
+        if !call.cheats.value.is_zero() {
+            write!(sentence, ", sending {}", format_ether(call.cheats.value))?;
+        }
+        sentence.push('.');
+
+        Ok(sentence)
+    }
+
+    /// The alias of a sender if it has one, its address otherwise
+    fn sender_name(&self, sender: &str) -> String {
+        match self.options.actors.get(&sender.to_lowercase()) {
+            Some(alias) => alias.clone(),
+            None => format!("`{}`", sender),
+        }
+    }
+}
+
+impl EmitterBackend for NarrationBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let fn_declarations: Vec<_> = reproducers
+            .iter()
+            .filter_map(|ast| match ast {
+                Ast::FunctionDeclaration(fn_declaration) => Some(fn_declaration),
+                Ast::Statement(_) => None,
+            })
+            .collect();
+
+        let mut contracts: Vec<&str> = Vec::new();
+        for call in fn_declarations
+            .iter()
+            .flat_map(|fn_declaration| fn_declaration.calls())
+        {
+            if !contracts.contains(&call.contract.as_str()) {
+                contracts.push(&call.contract);
+            }
+        }
+
+        let abi = match &self.options.artifacts_dir {
+            Some(artifacts_dir) => AbiIndex::load(artifacts_dir, &contracts)
+                .context("Failed to load the contracts abi")?,
+            None => AbiIndex::default(),
+        };
+
+        let mut narration = String::new();
+
+        for fn_declaration in fn_declarations {
+            writeln!(narration, "### {}\n", fn_declaration.name())?;
+
+            let calls: Vec<&CallContext> = fn_declaration.calls().collect();
+            for (step, call) in calls.iter().enumerate() {
+                let previous = step.checked_sub(1).map(|previous| calls[previous]);
+                writeln!(
+                    narration,
+                    "{}. {}",
+                    step + 1,
+                    self.sentence(call, previous, &abi)?
+                )?;
+            }
+
+            if let Some(failed_property) = fn_declaration.failed_property() {
+                writeln!(
+                    narration,
+                    "\nThe property `{}.{}` then fails ({} failure).",
+                    failed_property.contract, failed_property.property, failed_property.kind
+                )?;
+            }
+            narration.push('\n');
+        }
+
+        write!(writer, "{}", narration).context("Error: Failed to write the narration")?;
+
+        Ok(())
+    }
+}
+
+/// "1 block", "3 blocks"
+fn plural(count: i64, unit: &str) -> String {
+    match count.abs() {
+        1 => format!("{} {}", count, unit),
+        _ => format!("{} {}s", count, unit),
+    }
+}
+
+/// A duration in its two largest units, eg "3 days and 11 hours"
+fn human_duration(seconds: i64) -> String {
+    let units = [
+        ("day", 86_400),
+        ("hour", 3_600),
+        ("minute", 60),
+        ("second", 1),
+    ];
+
+    let mut remaining = seconds.abs();
+    let mut parts = Vec::new();
+    for (unit, length) in units {
+        if remaining >= length {
+            parts.push(plural(remaining / length, unit));
+            remaining %= length;
+        }
+    }
+    parts.truncate(2);
+
+    let duration = parts.join(" and ");
+    if seconds < 0 {
+        format!("minus {}", duration)
+    } else {
+        duration
+    }
+}
+
+/// A wei amount in the most readable unit: wei, gwei (from 1 gwei) or ETH (from 0.001 ETH)
+fn format_ether(wei: U256) -> String {
+    let (decimals, unit) = if wei < U256::exp10(9) {
+        (0, "wei")
+    } else if wei < U256::exp10(15) {
+        (9, "gwei")
+    } else {
+        (18, "ETH")
+    };
+
+    let scale = U256::exp10(decimals);
+    let integer = wei / scale;
+    let fraction = format!("{:0>width$}", (wei % scale).to_string(), width = decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    match fraction {
+        "" => format!("{} {}", integer, unit),
+        fraction => format!("{}.{} {}", integer, fraction, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CheatsData, TypedArgument};
+
+    fn call(block: i32, time: i32, value: u64) -> CallContext {
+        CallContext {
+            contract: "FuzzTest".to_string(),
+            function: "withdraw".to_string(),
+            signature: Some("withdraw(uint256)".to_string()),
+            arguments: vec![TypedArgument {
+                type_: "uint256".to_string(),
+                value: "100".to_string(),
+            }],
+            raw_arguments: "100".to_string(),
+            cheats: CheatsData {
+                block_to_roll: block,
+                timestamp_to_warp_to: time,
+                caller_to_prank: "0x00000000000000000000000000000000000A0000".to_string(),
+                value: U256::from(value),
+                gas: None,
+                gas_price: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_sentence() {
+        let mut options = EmitterOptions::default();
+        options.actors.insert(
+            "0x00000000000000000000000000000000000a0000".to_string(),
+            "Bob".to_string(),
+        );
+        let backend = NarrationBackend::new(options);
+
+        assert_eq!(
+            backend
+                .sentence(&call(1, 10, 0), None, &AbiIndex::default())
+                .unwrap(),
+            "At block 1 (timestamp 10), Bob calls `withdraw(100)`."
+        );
+        assert_eq!(
+            backend
+                .sentence(
+                    &call(2, 259_210, 5),
+                    Some(&call(1, 10, 0)),
+                    &AbiIndex::default()
+                )
+                .unwrap(),
+            "Then, 3 days later (1 block), Bob calls `withdraw(100)`, sending 5 wei."
+        );
+        assert_eq!(
+            backend
+                .sentence(&call(1, 10, 0), Some(&call(1, 10, 0)), &AbiIndex::default())
+                .unwrap(),
+            "In the same block, Bob calls `withdraw(100)`."
+        );
+    }
+
+    #[test]
+    fn test_human_duration() {
+        assert_eq!(human_duration(1), "1 second");
+        assert_eq!(human_duration(3_725), "1 hour and 2 minutes");
+        assert_eq!(human_duration(301_781), "3 days and 11 hours");
+    }
+
+    #[test]
+    fn test_format_ether() {
+        assert_eq!(format_ether(U256::from(123)), "123 wei");
+        assert_eq!(format_ether(U256::exp10(10)), "10 gwei");
+        assert_eq!(
+            format_ether(U256::from_dec_str("12345678901234567890").unwrap()),
+            "12.34567890123456789 ETH"
+        );
+        assert_eq!(format_ether(U256::exp10(18)), "1 ETH");
+    }
+}
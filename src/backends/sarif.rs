@@ -0,0 +1,157 @@
+use crate::ast::Ast;
+use crate::backends::{group_by_property, EmitterBackend, PropertyFailures};
+use crate::emitter::EmitterOptions;
+use crate::sources::{to_slash, SourceIndex};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Emit a SARIF 2.1.0 log, one result per failing property
+/// Results are located at the property declaration when it is found in the sources
+pub struct SarifBackend {
+    options: EmitterOptions,
+}
+
+impl SarifBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    fn result(failures: &PropertyFailures, sources: &SourceIndex) -> Value {
+        let shortest = failures
+            .reproducers
+            .iter()
+            .map(|(_, fn_declaration)| fn_declaration.calls().count())
+            .min()
+            .unwrap_or_default();
+        let reproducers: Vec<&str> = failures
+            .reproducers
+            .iter()
+            .map(|(_, fn_declaration)| fn_declaration.name())
+            .collect();
+
+        let mut result = json!({
+            "ruleId": rule_id(failures),
+            "level": "error",
+            "message": {
+                "text": format!(
+                    "Property {} failed after {} calls (reproducers: {})",
+                    failures.property(),
+                    shortest,
+                    reproducers.join(", ")
+                )
+            },
+        });
+
+        let location =
+            sources.find_function(failures.contract().unwrap_or_default(), failures.property());
+        if let Some(location) = location {
+            result["locations"] = json!([{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": to_slash(&location.path) },
+                    "region": { "startLine": location.line }
+                }
+            }]);
+        }
+
+        result
+    }
+}
+
+impl EmitterBackend for SarifBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let roots: Vec<&Path> = self
+            .options
+            .source_dirs
+            .iter()
+            .map(|dir| dir.as_path())
+            .collect();
+        let sources = SourceIndex::scan(&roots).context("Failed to scan the Solidity sources")?;
+
+        let failures = group_by_property(reproducers);
+
+        let mut rules: Vec<String> = failures.iter().map(rule_id).collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "youdusa",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/defi-wonderland/youdusa-rs",
+                        "rules": rules
+                            .iter()
+                            .map(|rule| json!({
+                                "id": rule,
+                                "shortDescription": { "text": format!("Medusa {}", rule.replace('-', " ")) }
+                            }))
+                            .collect::<Vec<_>>()
+                    }
+                },
+                "results": failures
+                    .iter()
+                    .map(|failure| Self::result(failure, &sources))
+                    .collect::<Vec<_>>()
+            }]
+        });
+
+        serde_json::to_writer_pretty(&mut *writer, &log)
+            .context("Error: Failed to serialize the SARIF log")?;
+        writeln!(writer).context("Error: Failed to write the SARIF log")?;
+
+        Ok(())
+    }
+}
+
+/// The rule broken, after the failure kind (eg `assertion-failure`)
+fn rule_id(failures: &PropertyFailures) -> String {
+    let kind = failures
+        .failed_property
+        .map(|failed_property| failed_property.kind.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    format!("{}-failure", kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::FunctionDeclaration;
+    use crate::types::{FailedProperty, FailureKind};
+
+    #[test]
+    fn test_emit_without_sources() {
+        let reproducers = vec![Ast::FunctionDeclaration(
+            FunctionDeclaration::new("test_prop_a").with_failed_property(FailedProperty {
+                contract: "FuzzTest".to_string(),
+                property: "prop_a".to_string(),
+                kind: FailureKind::Property,
+            }),
+        )];
+        let mut backend = SarifBackend::new(EmitterOptions::default());
+        let mut output = Vec::new();
+
+        backend.emit_reproducers(&reproducers, &mut output).unwrap();
+
+        let log: Value = serde_json::from_slice(&output).unwrap();
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "property-failure");
+        assert_eq!(
+            result["message"]["text"],
+            "Property prop_a failed after 0 calls (reproducers: test_prop_a)"
+        );
+        assert!(result.get("locations").is_none());
+        assert_eq!(
+            log["runs"][0]["tool"]["driver"]["rules"][0]["id"],
+            "property-failure"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::ast::{Ast, FunctionCall, Statement};
+use crate::backends::EmitterBackend;
+use crate::emitter::EmitterOptions;
+use crate::sources::{to_slash, SourceIndex};
+
+use anyhow::{anyhow, Context, Result};
+use primitive_types::U256;
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+use std::path::Path;
+
+/// Emit a forge-std Script contract, whose `run()` replays every reproducer, broadcasting each
+/// call from its original sender to a deployed harness (its address read from an env var)
+/// @dev roll and warp have no effect on a broadcast, the original block and time are kept as comments
+pub struct ScriptBackend {
+    options: EmitterOptions,
+}
+
+impl ScriptBackend {
+    pub fn new(options: EmitterOptions) -> Self {
+        Self { options }
+    }
+
+    /// A broadcast harness call, from the original sender
+    /// A call without Medusa context (ie not parsed from a log) is an error, rather than a gap in the sequence
+    fn emit_call(call: &FunctionCall, harnesses: &[String], script: &mut String) -> Result<()> {
+        let context = call.context.as_ref().ok_or_else(|| {
+            anyhow!(
+                "Cannot replay {}: its sender and harness are unknown (no Medusa context)",
+                call.function_name
+            )
+        })?;
+
+        writeln!(
+            script,
+            "        // block {}, time {}",
+            context.cheats.block_to_roll, context.cheats.timestamp_to_warp_to
+        )?;
+        writeln!(
+            script,
+            "        vm.startBroadcast(address({}));",
+            context.cheats.caller_to_prank
+        )?;
+
+        write!(
+            script,
+            "        {}.{}",
+            harness_variable(&context.contract, harnesses),
+            call.function_name
+        )?;
+        if let Some(value) = call.value.filter(|value| *value != U256::zero()) {
+            write!(script, "{{ value: {} }}", value)?;
+        }
+        writeln!(script, "({});", call.arguments.join(", "))?;
+
+        writeln!(script, "        vm.stopBroadcast();\n")?;
+
+        Ok(())
+    }
+}
+
+impl EmitterBackend for ScriptBackend {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        let roots: Vec<&Path> = self
+            .options
+            .source_dirs
+            .iter()
+            .map(|dir| dir.as_path())
+            .collect();
+        let sources = SourceIndex::scan(&roots).context("Failed to scan the Solidity sources")?;
+
+        let fn_declarations: Vec<_> = reproducers
+            .iter()
+            .filter_map(|ast| match ast {
+                Ast::FunctionDeclaration(fn_declaration) => Some(fn_declaration),
+                Ast::Statement(_) => None,
+            })
+            .collect();
+
+        let mut harnesses: Vec<String> = Vec::new();
+        for call in fn_declarations
+            .iter()
+            .flat_map(|fn_declaration| fn_declaration.calls())
+        {
+            if !harnesses.contains(&call.contract) {
+                harnesses.push(call.contract.clone());
+            }
+        }
+
+        let mut script = String::from("// SPDX-License-Identifier: UNLICENSED\n");
+        writeln!(
+            script,
+            "pragma solidity {};\n",
+            self.options.pragma.as_deref().unwrap_or("^0.8.0")
+        )?;
+        writeln!(script, "import {{Script}} from 'forge-std/Script.sol';")?;
+        for harness in &harnesses {
+            // Forge resolves paths relative to the project root, as well as to the script
+            let path = sources
+                .find_contract(harness)
+                .map(to_slash)
+                .unwrap_or_else(|| format!("./{}.t.sol", harness));
+            writeln!(script, "import {{{}}} from '{}';", harness, path)?;
+        }
+
+        writeln!(script)?;
+        writeln!(
+            script,
+            "/// @notice Replay the failing sequences found by Medusa against deployed harnesses"
+        )?;
+        writeln!(
+            script,
+            "/// @dev The senders need to be unlocked, eg `anvil --auto-impersonate` then `forge script --broadcast --unlocked`"
+        )?;
+        writeln!(script, "contract ReproducerScript is Script {{")?;
+        for harness in &harnesses {
+            writeln!(
+                script,
+                "    {} internal {};",
+                harness,
+                harness_variable(harness, &harnesses)
+            )?;
+        }
+
+        writeln!(script, "\n    function setUp() public {{")?;
+        for harness in &harnesses {
+            writeln!(
+                script,
+                "        {} = {}(payable(vm.envAddress('{}')));",
+                harness_variable(harness, &harnesses),
+                harness,
+                harness_env_variable(harness, &harnesses)
+            )?;
+        }
+        writeln!(script, "    }}\n")?;
+
+        writeln!(script, "    function run() public {{")?;
+        for fn_declaration in &fn_declarations {
+            writeln!(script, "        {}();", fn_declaration.name())?;
+        }
+        writeln!(script, "    }}")?;
+
+        for fn_declaration in &fn_declarations {
+            writeln!(
+                script,
+                "\n    function {}() public {{",
+                fn_declaration.name()
+            )?;
+            for child in fn_declaration.children() {
+                if let Ast::Statement(Statement::ContractCall(call)) = child {
+                    Self::emit_call(call, &harnesses, &mut script)?;
+                }
+            }
+            writeln!(script, "    }}")?;
+        }
+
+        writeln!(script, "}}")?;
+
+        write!(writer, "{}", script).context("Error: Failed to write the script")?;
+
+        Ok(())
+    }
+}
+
+/// `harness`, or `harness<Contract>` if there are multiple harnesses
+fn harness_variable(contract: &str, harnesses: &[String]) -> String {
+    match harnesses.len() {
+        1 => "harness".to_string(),
+        _ => format!("harness{}", contract),
+    }
+}
+
+/// `HARNESS`, or `HARNESS_<CONTRACT>` if there are multiple harnesses
+fn harness_env_variable(contract: &str, harnesses: &[String]) -> String {
+    match harnesses.len() {
+        1 => "HARNESS".to_string(),
+        _ => format!("HARNESS_{}", contract.to_uppercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harness_variables() {
+        let single = vec!["FuzzTest".to_string()];
+        let multiple = vec!["FuzzTest".to_string(), "OtherTest".to_string()];
+
+        assert_eq!(harness_variable("FuzzTest", &single), "harness");
+        assert_eq!(harness_env_variable("FuzzTest", &single), "HARNESS");
+        assert_eq!(harness_variable("OtherTest", &multiple), "harnessOtherTest");
+        assert_eq!(
+            harness_env_variable("OtherTest", &multiple),
+            "HARNESS_OTHERTEST"
+        );
+    }
+
+    #[test]
+    fn test_emit_call_without_context() {
+        let Statement::ContractCall(call) = Statement::new_contract_call(
+            Some("this".to_string()),
+            "prop_foo".to_string(),
+            None,
+            Vec::new(),
+            None,
+        ) else {
+            panic!("not a contract call");
+        };
+        let mut script = String::new();
+
+        let error = ScriptBackend::emit_call(&call, &["FuzzTest".to_string()], &mut script);
+
+        assert!(error
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot replay prop_foo"));
+        assert!(script.is_empty());
+    }
+}
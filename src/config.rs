@@ -0,0 +1,193 @@
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use toml::Table;
+use youdusa::{actor_constant, Origin};
+
+/// The youdusa.toml settings, used as defaults for the command line options
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// Path to a custom reproducer contract template (`template`)
+    pub template: Option<String>,
+
+    /// The environment variable holding the fork RPC URL (`fork_rpc_env`)
+    pub fork_rpc_env: Option<String>,
+
+    /// Names given to the senders (`[aliases]`, `"0x..." = "Alice"`), by full lowercased address
+    pub aliases: BTreeMap<String, String>,
+
+    /// Roll and warp relative to the current block (`relative`)
+    pub relative: bool,
+
+    /// The block and timestamp the harness starts at, for the relative mode (`[origin]`,
+    /// `block` and `timestamp`, 0 if missing)
+    pub origin: Origin,
+}
+
+impl Config {
+    /// Load youdusa.toml from the current directory, or the defaults if there is none
+    pub fn load() -> Result<Config> {
+        let config_file = "youdusa.toml";
+
+        if !Path::new(config_file).exists() {
+            return Ok(Config::default());
+        }
+
+        let contents = fs::read_to_string(config_file).context("Failed to read youdusa.toml")?;
+        Config::from_toml_str(&contents)
+    }
+
+    fn from_toml_str(contents: &str) -> Result<Config> {
+        let parsed: Table = contents.parse().context("Failed to parse youdusa.toml")?;
+
+        let aliases = parsed
+            .get("aliases")
+            .and_then(|aliases| aliases.as_table())
+            .map(|aliases| {
+                aliases
+                    .iter()
+                    .filter_map(|(address, alias)| {
+                        Some((normalize_address(address)?, alias.as_str()?.to_owned()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        check_aliases(&aliases)?;
+
+        Ok(Config {
+            template: parsed
+                .get("template")
+                .and_then(|template| template.as_str())
+                .map(str::to_owned),
+            fork_rpc_env: parsed
+                .get("fork_rpc_env")
+                .and_then(|fork_rpc_env| fork_rpc_env.as_str())
+                .map(str::to_owned),
+            aliases,
+            relative: parsed
+                .get("relative")
+                .and_then(|relative| relative.as_bool())
+                .unwrap_or_default(),
+            origin: parsed
+                .get("origin")
+                .and_then(|origin| origin.as_table())
+                .map(|origin| Origin {
+                    block: origin
+                        .get("block")
+                        .and_then(|block| block.as_integer())
+                        .and_then(|block| u64::try_from(block).ok())
+                        .unwrap_or_default(),
+                    timestamp: origin
+                        .get("timestamp")
+                        .and_then(|timestamp| timestamp.as_integer())
+                        .and_then(|timestamp| u64::try_from(timestamp).ok())
+                        .unwrap_or_default(),
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Two aliases can't share a name, nor an actor constant (eg "Alice B" and "alice-b" are both `ACTOR_ALICE_B`)
+fn check_aliases(aliases: &BTreeMap<String, String>) -> Result<()> {
+    let mut constants: BTreeMap<String, &str> = BTreeMap::new();
+
+    for alias in aliases.values() {
+        let constant = actor_constant(alias);
+        if let Some(taken) = constants.get(&constant) {
+            bail!(
+                "The aliases {:?} and {:?} would both be named {}, rename one of them",
+                taken,
+                alias,
+                constant
+            );
+        }
+        constants.insert(constant, alias);
+    }
+
+    Ok(())
+}
+
+/// A full lowercased address, as printed by Medusa: "0x10000" is "0x0000000000000000000000000000000000010000"
+/// None if not an address
+pub fn normalize_address(address: &str) -> Option<String> {
+    let digits = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))?;
+
+    if digits.is_empty() || digits.len() > 40 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(format!("0x{:0>40}", digits.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml_str() {
+        let config = Config::from_toml_str("template = \"templates/custom.sol\"").unwrap();
+
+        assert_eq!(config.template, Some("templates/custom.sol".to_owned()));
+    }
+
+    #[test]
+    fn test_from_toml_str_aliases() {
+        let config = Config::from_toml_str(
+            "[aliases]\n\"0x00000000000000000000000000000000000A0000\" = \"Alice\"\n\"0x20000\" = \"Bob\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.aliases,
+            BTreeMap::from([
+                (
+                    "0x00000000000000000000000000000000000a0000".to_owned(),
+                    "Alice".to_owned()
+                ),
+                (
+                    "0x0000000000000000000000000000000000020000".to_owned(),
+                    "Bob".to_owned()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_from_toml_str_colliding_aliases() {
+        let error = Config::from_toml_str(
+            "[aliases]\n\"0x10000\" = \"Alice B\"\n\"0x20000\" = \"alice-b\"",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The aliases \"Alice B\" and \"alice-b\" would both be named ACTOR_ALICE_B, rename one of them"
+        );
+    }
+
+    #[test]
+    fn test_from_toml_str_origin() {
+        let config = Config::from_toml_str(
+            "relative = true\n[origin]\nblock = 18000000\ntimestamp = 1700000000",
+        )
+        .unwrap();
+
+        assert!(config.relative);
+        assert_eq!(
+            config.origin,
+            Origin {
+                block: 18_000_000,
+                timestamp: 1_700_000_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_toml_str_empty() {
+        assert_eq!(Config::from_toml_str("").unwrap(), Config::default());
+    }
+}
@@ -1,94 +1,782 @@
 use anyhow::{anyhow, Context, Result};
 use askama::Template;
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::{self, File};
 use std::io::Write as WriteIO;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use youdusa::ast::{Ast, FunctionDeclaration};
+use youdusa::backends::EmitterBackend;
+use youdusa::sources::SourceIndex;
+use youdusa::{actor_constant, Emitter, EmitterOptions, Profile};
+
+use crate::config::normalize_address;
+use crate::foundry_config::FoundryConfig;
+
+/// How the reproducers are spread across the generated contract files
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum SplitBy {
+    /// One contract per failing property
+    Property,
+    /// One contract per contract declaring failing properties
+    Contract,
+    /// A single contract containing every reproducer
+    #[default]
+    None,
+}
+
+/// How and where the reproducer contracts are written
+#[derive(Debug, Clone, Default)]
+pub struct WriterOptions {
+    pub split_by: SplitBy,
+
+    /// Path to a custom template, rendered instead of the built-in one
+    pub template: Option<String>,
+
+    /// Where the Medusa log comes from (file name or stdin), for the run metadata
+    pub input: String,
+
+    /// The environment variable holding the RPC URL, when medusa.json enables forking
+    /// (`RPC_URL` if None)
+    pub fork_rpc_env: Option<String>,
+
+    /// How the reproducer functions are emitted
+    pub emitter: EmitterOptions,
+
+    /// Name the senders and deployer of medusa.json with constants, labelled by the reproducers
+    pub actors: bool,
+
+    /// The foundry.toml settings of the project
+    pub foundry_config: FoundryConfig,
+}
+
+/// The environment variable holding the fork RPC URL, if not configured
+const DEFAULT_FORK_RPC_ENV: &str = "RPC_URL";
+
+/// The on-chain fork Medusa fuzzed on (`fuzzing.chainConfig.forkConfig` in medusa.json)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForkConfig {
+    /// The fork block, None for the latest one
+    pub block: Option<u64>,
+}
 
 /// The contract template,
-#[derive(Template, Debug, Clone, PartialEq)]
+/// The fields are the variables available in the built-in and custom templates (except path)
+#[derive(Template, Serialize, Debug, Clone, PartialEq)]
 #[template(path = "template.sol", escape = "none")]
 pub struct Contract {
+    /// The reproducer functions, without trailing newline (the template closes the contract on the next line)
     reproducers: String,
     contract_name: String,
+    #[serde(skip)]
     path: String,
+    pragma: String,
+    imports: String,
+    harness: String,
+    reproducer_count: usize,
+    input: String,
+    generated_at: String,
+    youdusa_version: String,
+    standalone: bool,
+    chimera: bool,
+    extension: String,
+
+    /// The statement selecting the fork in setUp, empty without fork or when the harness is inherited
+    fork: String,
+
+    /// The actor constant declarations (`address constant ACTOR_USER1 = ...;`), one per line
+    constants: String,
+
+    /// The `vm.label` calls naming the actors, one per line, the body of the `_labelActors()`
+    /// helper called by the reproducers
+    labels: String,
+}
+
+/// What is known about the project the reproducers are written into
+pub struct ProjectContext {
+    foundry_config: FoundryConfig,
+
+    /// The Solidity sources, to locate the harness and cheatcodes handle
+    sources: SourceIndex,
+
+    /// The directory where the reproducers are written
+    target_path: String,
+
+    /// The custom template content, if any
+    template: Option<String>,
+
+    /// Where the Medusa log comes from
+    input: String,
+
+    /// Deploy the harness in the reproducer contract, rather than inheriting it
+    standalone: bool,
+
+    profile: Profile,
+
+    /// The fork to select in setUp, if medusa.json enables forking
+    fork: Option<ForkConfig>,
+
+    /// The environment variable holding the fork RPC URL
+    fork_rpc_env: String,
+
+    /// How the reproducer functions are emitted, the actors completed with the medusa.json ones
+    emitter: EmitterOptions,
+}
+
+impl ProjectContext {
+    pub fn load(options: &WriterOptions) -> Result<ProjectContext> {
+        let foundry_config = options.foundry_config.clone();
+
+        let sources = SourceIndex::scan(&[
+            Path::new(&foundry_config.test),
+            Path::new(&foundry_config.src),
+        ])
+        .context("Failed to scan the Solidity sources")?;
+
+        // Chimera reproducers live with the rest of the Recon suite
+        let default_target = match options.emitter.profile {
+            Profile::Foundry => format!("{}/invariants/fuzz/", foundry_config.test),
+            Profile::Chimera => format!("{}/recon/", foundry_config.test),
+        };
+        let medusa_config = read_medusa_config()?;
+        let target_path = get_target_path(default_target, medusa_config.as_ref())
+            .context("Failed to get target path")?;
+        let fork = medusa_config.as_ref().and_then(get_fork_config);
+
+        let template = options
+            .template
+            .as_ref()
+            .map(|template| {
+                fs::read_to_string(template)
+                    .with_context(|| format!("Failed to read template {}", template))
+            })
+            .transpose()?;
+
+        let project = ProjectContext {
+            foundry_config,
+            sources,
+            target_path,
+            template,
+            input: options.input.clone(),
+            standalone: options.emitter.standalone,
+            profile: options.emitter.profile,
+            fork,
+            fork_rpc_env: options
+                .fork_rpc_env
+                .clone()
+                .unwrap_or_else(|| DEFAULT_FORK_RPC_ENV.to_owned()),
+            emitter: EmitterOptions {
+                actors: match options.actors {
+                    true => get_actors(medusa_config.as_ref(), &options.emitter.actors),
+                    false => options.emitter.actors.clone(),
+                },
+                actor_constants: options.actors,
+                ..options.emitter.clone()
+            },
+        };
+
+        if let Some(fork) = project.fork.as_ref().filter(|_| !project.selects_fork()) {
+            let block = fork
+                .block
+                .map(|block| format!(" --fork-block-number {}", block))
+                .unwrap_or_default();
+            eprintln!(
+                "Note: medusa.json forks a chain, run the reproducers with `forge test --fork-url \"${}\"{}` \
+                 (or eth_rpc_url in foundry.toml), or use --standalone to select the fork in setUp",
+                project.fork_rpc_env, block
+            );
+        }
+
+        Ok(project)
+    }
+
+    /// The actors named by constants, by lowercased address (none without `--actors`)
+    fn actors(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.emitter
+            .actors
+            .iter()
+            .filter(|_| self.emitter.actor_constants)
+    }
+
+    /// `address constant ACTOR_USER1 = 0x...;`, for every actor
+    fn actor_constants(&self) -> String {
+        self.actors()
+            .map(|(address, name)| {
+                format!(
+                    "    address constant {} = {};",
+                    actor_constant(name),
+                    address_literal(address)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `vm.label(ACTOR_USER1, 'USER1');`, for every actor
+    /// @dev `vm` even for Chimera, like the fork selection: its contract inherits forge-std `Test`
+    fn actor_labels(&self) -> String {
+        self.actors()
+            .map(|(_, name)| {
+                format!(
+                    "        vm.label({}, {});",
+                    actor_constant(name),
+                    string_literal(name)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Whether the fork is selected in setUp: not when the harness is inherited, as its constructor
+    /// deploys the targets before setUp, and they would be lost on the fork
+    fn selects_fork(&self) -> bool {
+        self.standalone || self.profile == Profile::Chimera
+    }
+
+    /// `vm.createSelectFork(vm.envString('RPC_URL'), block);`, empty without fork (or if not selected in setUp)
+    /// @dev `vm` even for Chimera, hevm having no envString nor createSelectFork (its contract inherits forge-std `Test`)
+    fn fork_setup(&self) -> String {
+        let Some(fork) = self.fork.as_ref().filter(|_| self.selects_fork()) else {
+            return String::new();
+        };
+
+        let rpc_url = format!("vm.envString('{}')", self.fork_rpc_env);
+        match fork.block {
+            Some(block) => format!("vm.createSelectFork({}, {});", rpc_url, block),
+            None => format!("vm.createSelectFork({});", rpc_url),
+        }
+    }
+
+    /// The default base name of the reproducer contract
+    fn default_contract_name(&self) -> &'static str {
+        match self.profile {
+            Profile::Foundry => "ForgeReproducer",
+            Profile::Chimera => "CryticToFoundry",
+        }
+    }
+
+    /// The extension of the reproducer files (Recon doesn't use the .t.sol convention)
+    fn file_extension(&self) -> &'static str {
+        match self.profile {
+            Profile::Foundry => ".t.sol",
+            Profile::Chimera => ".sol",
+        }
+    }
+
+    /// The contracts the reproducer contract inherits or deploys.
+    /// Chimera reproducers always inherit the target functions, rather than CryticTester
+    fn harnesses(&self, group: &[&Ast]) -> Vec<String> {
+        match self.profile {
+            Profile::Foundry => harnesses_of(group),
+            Profile::Chimera => vec!["TargetFunctions".to_owned()],
+        }
+    }
+
+    /// The (symbol, path) to import for a reproducer contract inheriting the harnesses.
+    /// The harnesses are imported from the file declaring them, `{harness}.t.sol` in the target
+    /// directory if not found. The vm handle is imported from the file declaring it as a constant,
+    /// or assumed to be inherited when the harnesses were found elsewhere (eg from forge-std Test).
+    /// Standalone reproducers get vm from forge-std Test instead.
+    /// Chimera reproducers import Test and FoundryAsserts, and hevm if declared as a constant.
+    fn imports(&self, harnesses: &[String]) -> Vec<(String, String)> {
+        let target_dir = Path::new(&self.target_path);
+        let mut all_harnesses_found = true;
+
+        let mut imports: Vec<(String, String)> = harnesses
+            .iter()
+            .map(|harness| {
+                let harness_path = match self.sources.find_contract(harness) {
+                    Some(path) => path.to_path_buf(),
+                    None => {
+                        all_harnesses_found = false;
+                        target_dir.join(format!("{}{}", harness, self.file_extension()))
+                    }
+                };
+
+                (
+                    harness.clone(),
+                    self.foundry_config.import_path(&harness_path, target_dir),
+                )
+            })
+            .collect();
+
+        if self.profile == Profile::Chimera {
+            imports.insert(0, ("Test".to_owned(), "forge-std/Test.sol".to_owned()));
+            imports.push((
+                "FoundryAsserts".to_owned(),
+                "@chimera/FoundryAsserts.sol".to_owned(),
+            ));
+            if let Some(hevm_path) = self.sources.find_constant("hevm") {
+                imports.push((
+                    "hevm".to_owned(),
+                    self.foundry_config.import_path(hevm_path, target_dir),
+                ));
+            }
+            return imports;
+        }
+
+        if self.standalone {
+            imports.insert(0, ("Test".to_owned(), "forge-std/Test.sol".to_owned()));
+            return imports;
+        }
+
+        match self.sources.find_constant("vm") {
+            Some(vm_path) => imports.push((
+                "vm".to_owned(),
+                self.foundry_config.import_path(vm_path, target_dir),
+            )),
+            None if !all_harnesses_found => imports.push((
+                "vm".to_owned(),
+                self.foundry_config
+                    .import_path(&target_dir.join("Setup.t.sol"), target_dir),
+            )),
+            None => {}
+        }
+
+        imports
+    }
 }
 
 impl Contract {
-    pub fn new(reproducers: &[u8]) -> Result<Contract> {
-        let path = get_target_path().context("Failed to get target path")?;
+    /// Create a contract named after base_name, numbered if this name is already taken,
+    /// either by an existing file or by another contract of this run (in reserved_names)
+    pub fn with_base_name(
+        reproducers: &[u8],
+        reproducer_count: usize,
+        base_name: &str,
+        harnesses: &[String],
+        project: &ProjectContext,
+        reserved_names: &mut HashSet<String>,
+    ) -> Result<Contract> {
+        let path = project.target_path.clone();
 
-        let contract_name = Contract::find_first_unused_filename(path.clone())
-            .context("Failed to find a filename")?;
+        if project.standalone && harnesses.len() > 1 {
+            return Err(anyhow!(
+                "Standalone reproducers deploy a single harness, but {} are needed ({}), use --split-by contract",
+                harnesses.len(),
+                harnesses.join(", ")
+            ));
+        }
+
+        let extension = project.file_extension().to_owned();
+
+        let contract_name = Contract::find_first_unused_filename(
+            path.clone(),
+            base_name,
+            &extension,
+            reserved_names,
+        )
+        .context("Failed to find a filename")?;
 
         Ok(Contract {
-            reproducers: String::from_utf8_lossy(reproducers).to_string(),
+            reproducers: String::from_utf8_lossy(reproducers).trim_end().to_owned(),
             contract_name,
             path,
+            pragma: project.foundry_config.pragma(),
+            imports: render_imports(&project.imports(harnesses)),
+            harness: harnesses.join(", "),
+            reproducer_count,
+            input: project.input.clone(),
+            generated_at: today(),
+            youdusa_version: env!("CARGO_PKG_VERSION").to_owned(),
+            standalone: project.standalone,
+            chimera: project.profile == Profile::Chimera,
+            extension,
+            fork: project.fork_setup(),
+            constants: project.actor_constants(),
+            labels: project.actor_labels(),
         })
     }
 
-    pub fn write_rendered_contract(&self) -> Result<()> {
+    /// Render a custom template, with the same variables as the built-in one
+    pub fn render_custom(&self, template: &str) -> Result<String> {
+        render_template(template, self)
+    }
+
+    /// Write the contract, returning the path of the file written
+    pub fn write_rendered_contract(&self, template: Option<&str>) -> Result<PathBuf> {
         // Ensure the target directory exists (create it if not)
         fs::create_dir_all(&self.path).context("Failed to create target directory")?;
 
         // Construct the full filepath by combining the target directory and contract file name.
-        let file_name = format!("{}.t.sol", self.contract_name);
+        let file_name = format!("{}{}", self.contract_name, self.extension);
         let output_filepath = std::path::Path::new(&self.path).join(file_name);
 
         let mut f = File::create_new(&output_filepath).context("Failed to create contract file")?;
 
-        let rendered = self.render().context("Fail to render contract")?;
+        let rendered = match template {
+            Some(template) => self.render_custom(template),
+            None => self.render().map_err(Into::into),
+        }
+        .context("Fail to render contract")?;
 
         f.write_all(rendered.as_bytes())
             .context("Failed to write contract")?;
 
-        Ok(())
+        Ok(output_filepath)
     }
 
-    fn find_first_unused_filename(target_path: String) -> Result<String> {
+    /// @dev reserved names are compared lowercased, as some filesystems are case insensitive
+    fn find_first_unused_filename(
+        target_path: String,
+        base_name: &str,
+        extension: &str,
+        reserved_names: &mut HashSet<String>,
+    ) -> Result<String> {
         // Avoiding Regex intensifies
-        (0..)
+        let name = (0..)
             .map(|i| {
                 if i == 0 {
-                    "ForgeReproducer".to_owned()
+                    base_name.to_owned()
                 } else {
-                    format!("ForgeReproducer{}", i)
+                    format!("{}{}", base_name, i)
                 }
             })
-            .find(|base| !Path::new(&format!("{}{}{}", target_path, base, ".t.sol")).exists())
-            .ok_or_else(|| anyhow!("No available filename found"))
+            .find(|base| {
+                !reserved_names.contains(&base.to_lowercase())
+                    && !Path::new(&format!("{}{}{}", target_path, base, extension)).exists()
+            })
+            .ok_or_else(|| anyhow!("No available filename found"))?;
+
+        reserved_names.insert(name.to_lowercase());
+
+        Ok(name)
     }
 }
 
-/// Helper function to get the target directory from the medusa.json config.
-/// The "target" is expected under "compilation"->"platformConfig" in medusa.json.
-/// If the target contains a file (determined by checking for a file extension),
-/// the parent directory is returned. Otherwise, the target is assumed to be a directory.
-/// If medusa.json does not exist or no target is provided, the default
-/// "test/invariants/fuzz/" is returned.
-fn get_target_path() -> Result<String> {
-    let default_target = "test/invariants/fuzz/".to_owned();
+/// Write the reproducers in one or multiple contracts, depending on split_by, returning the files written
+pub fn write_reproducers(reproducers: &[Ast], options: &WriterOptions) -> Result<Vec<PathBuf>> {
+    let project = ProjectContext::load(options).context("Failed to load the project context")?;
+    let mut reserved_names = HashSet::new();
+    let mut written = Vec::new();
+
+    for (base_name, group) in group_reproducers(
+        reproducers,
+        options.split_by,
+        project.default_contract_name(),
+    ) {
+        let reproducer_count = group.len();
+        let harnesses = project.harnesses(&group);
+
+        let group: Vec<Ast> = group.into_iter().cloned().collect();
+        let mut emitted = Vec::new();
+        Emitter::new()
+            .with_options(project.emitter.clone())
+            .emit_reproducers(&group, &mut emitted)
+            .context("Failed to emit reproducers")?;
+
+        let path = Contract::with_base_name(
+            &emitted,
+            reproducer_count,
+            &base_name,
+            &harnesses,
+            &project,
+            &mut reserved_names,
+        )
+        .context("Contract init error")?
+        .write_rendered_contract(project.template.as_deref())
+        .context("Write error")?;
+
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Group the reproducers by contract to write, keeping the order in which they were found
+/// Each group comes with the base name of the contract to write it to
+fn group_reproducers<'a>(
+    reproducers: &'a [Ast],
+    split_by: SplitBy,
+    default_name: &str,
+) -> Vec<(String, Vec<&'a Ast>)> {
+    let mut groups: Vec<(String, Vec<&Ast>)> = Vec::new();
+
+    for ast in reproducers {
+        let key = match (split_by, ast) {
+            (SplitBy::None, _) => default_name.to_owned(),
+            (SplitBy::Property, Ast::FunctionDeclaration(fn_declaration)) => {
+                to_contract_name(&property_of(fn_declaration))
+            }
+            (SplitBy::Contract, Ast::FunctionDeclaration(fn_declaration)) => {
+                to_contract_name(&contract_of(fn_declaration))
+            }
+            (_, Ast::Statement(_)) => default_name.to_owned(),
+        };
+
+        match groups.iter_mut().find(|(name, _)| *name == key) {
+            Some((_, group)) => group.push(ast),
+            None => groups.push((key, vec![ast])),
+        }
+    }
+
+    // Nothing failed, but we still write an (empty) contract if everything goes in a single one
+    if groups.is_empty() && split_by == SplitBy::None {
+        groups.push((default_name.to_owned(), Vec::new()));
+    }
+
+    groups
+}
+
+/// Render a template with minijinja (`{{ variable }}`, `{% if standalone %}`, ...)
+/// Undefined variables are an error, rather than silently emitting broken Solidity
+fn render_template(template: &str, variables: impl Serialize) -> Result<String> {
+    let mut environment = Environment::new();
+    environment.set_undefined_behavior(UndefinedBehavior::Strict);
+    environment.set_keep_trailing_newline(true);
+
+    environment
+        .render_str(template, variables)
+        .context("Failed to render the template")
+}
+
+/// Today's UTC date, as YYYY-MM-DD
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+
+    civil_date(days)
+}
+
+/// The date (YYYY-MM-DD) of a number of days since the Unix epoch
+fn civil_date(days: i64) -> String {
+    // Days since epoch to civil date (see http://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The distinct contracts declaring the properties reproduced, which the reproducer contract inherits
+/// Defaults to FuzzTest if unknown
+fn harnesses_of(group: &[&Ast]) -> Vec<String> {
+    let mut harnesses: Vec<String> = Vec::new();
+
+    for ast in group {
+        if let Ast::FunctionDeclaration(fn_declaration) = ast {
+            if let Some(failed_property) = fn_declaration.failed_property() {
+                if !harnesses.contains(&failed_property.contract) {
+                    harnesses.push(failed_property.contract.clone());
+                }
+            }
+        }
+    }
+
+    if harnesses.is_empty() {
+        harnesses.push("FuzzTest".to_owned());
+    }
+
+    harnesses
+}
+
+/// Render the import lines of the contract, one per (symbol, path) pair
+fn render_imports(imports: &[(String, String)]) -> String {
+    imports
+        .iter()
+        .map(|(symbol, path)| format!("import {{{}}} from '{}';", symbol, path))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The property reproduced, falling back on the function name if unknown
+fn property_of(fn_declaration: &FunctionDeclaration) -> String {
+    fn_declaration
+        .failed_property()
+        .map(|failed| failed.property.clone())
+        .unwrap_or_else(|| fn_declaration.name().to_owned())
+}
+
+/// The contract declaring the property reproduced, falling back on the default contract name if unknown
+fn contract_of(fn_declaration: &FunctionDeclaration) -> String {
+    fn_declaration
+        .failed_property()
+        .map(|failed| failed.contract.clone())
+        .unwrap_or_else(|| "Forge".to_owned())
+}
+
+/// Build a valid Solidity contract name from a property or contract name:
+/// "prop_foo" becomes "Prop_fooReproducer"
+fn to_contract_name(raw: &str) -> String {
+    let mut name: String = raw
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        name.insert(0, 'R');
+    }
+
+    if let Some(first) = name.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+
+    format!("{}Reproducer", name)
+}
+
+/// Read medusa.json from the current directory, None if there is none
+fn read_medusa_config() -> Result<Option<Value>> {
     let medusa_file = "medusa.json";
 
     if !Path::new(medusa_file).exists() {
-        return Ok(default_target);
+        return Ok(None);
     }
 
     let medusa_contents = fs::read_to_string(medusa_file).context("Failed to read medusa.json")?;
     let parsed: Value =
         serde_json::from_str(&medusa_contents).context("Failed to parse medusa.json")?;
 
+    Ok(Some(parsed))
+}
+
+/// The fork settings of the medusa.json config, None if forking is not enabled
+/// (`forkModeEnabled` under "fuzzing"->"chainConfig"->"forkConfig").
+/// The RPC URL itself is not kept, the reproducers read it from an environment variable.
+/// A `rpcBlock` of 0 (or none) is the latest block.
+fn get_fork_config(medusa_config: &Value) -> Option<ForkConfig> {
+    let fork_config = medusa_config
+        .get("fuzzing")
+        .and_then(|fuzzing| fuzzing.get("chainConfig"))
+        .and_then(|chain| chain.get("forkConfig"))?;
+
+    if !fork_config
+        .get("forkModeEnabled")
+        .and_then(|enabled| enabled.as_bool())
+        .unwrap_or_default()
+    {
+        return None;
+    }
+
+    Some(ForkConfig {
+        block: fork_config
+            .get("rpcBlock")
+            .and_then(|block| block.as_u64())
+            .filter(|block| *block != 0),
+    })
+}
+
+/// Medusa's default senders and deployer, when medusa.json doesn't set them
+const DEFAULT_SENDERS: [&str; 3] = ["0x10000", "0x20000", "0x30000"];
+const DEFAULT_DEPLOYER: &str = "0x30000";
+
+/// Name the senders (`USER1`, `USER2`, ...) and deployer (`DEPLOYER`) of the medusa.json config
+/// ("fuzzing"->"senderAddresses" and "deployerAddress", Medusa's defaults if missing), the aliases
+/// taking precedence.
+/// Returns the names by (full, lowercased) address, the first name given to an address (or constant) winning
+fn get_actors(
+    medusa_config: Option<&Value>,
+    aliases: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let fuzzing = medusa_config.and_then(|medusa_config| medusa_config.get("fuzzing"));
+
+    let senders: Vec<&str> = fuzzing
+        .and_then(|fuzzing| fuzzing.get("senderAddresses"))
+        .and_then(|senders| senders.as_array())
+        .map(|senders| {
+            senders
+                .iter()
+                .filter_map(|sender| sender.as_str())
+                .collect()
+        })
+        .unwrap_or_else(|| DEFAULT_SENDERS.to_vec());
+    let deployer = fuzzing
+        .and_then(|fuzzing| fuzzing.get("deployerAddress"))
+        .and_then(|deployer| deployer.as_str())
+        .unwrap_or(DEFAULT_DEPLOYER);
+
+    let named = aliases
+        .iter()
+        .map(|(address, alias)| (address.as_str(), alias.clone()))
+        .chain(
+            senders
+                .into_iter()
+                .enumerate()
+                .map(|(index, sender)| (sender, format!("USER{}", index + 1))),
+        )
+        .chain([(deployer, "DEPLOYER".to_owned())]);
+
+    let mut actors: BTreeMap<String, String> = BTreeMap::new();
+    for (address, name) in named {
+        let Some(address) = normalize_address(address) else {
+            continue;
+        };
+
+        if !actors.contains_key(&address)
+            && !actors
+                .values()
+                .any(|taken| actor_constant(taken) == actor_constant(&name))
+        {
+            actors.insert(address, name);
+        }
+    }
+
+    actors
+}
+
+/// An address literal for a lowercased address: Solidity requires the letters of address
+/// literals to be checksummed, so these are cast from an integer instead
+fn address_literal(address: &str) -> String {
+    if address[2..].chars().all(|c| c.is_ascii_digit()) {
+        address.to_owned()
+    } else {
+        format!("address(uint160({}))", address)
+    }
+}
+
+/// A Solidity string literal: "O'Brien" is `'O\'Brien'`, the characters Solidity only accepts in
+/// unicode literals (non ASCII or control ones) being escaped as `\uNNNN` (or `?` outside of the BMP)
+fn string_literal(content: &str) -> String {
+    let escaped: String = content
+        .chars()
+        .map(|c| match c {
+            '\\' => "\\\\".to_owned(),
+            '\'' => "\\'".to_owned(),
+            ' '..='~' => c.to_string(),
+            c if (c as u32) <= 0xFFFF => format!("\\u{:04x}", c as u32),
+            _ => "?".to_owned(),
+        })
+        .collect();
+
+    format!("'{}'", escaped)
+}
+
+/// Helper function to get the target directory from the medusa.json config.
+/// The "target" is expected under "compilation"->"platformConfig" in medusa.json.
+/// If the target contains a file (determined by checking for a file extension),
+/// the parent directory is returned. Otherwise, the target is assumed to be a directory.
+/// If medusa.json does not exist or no target is provided, the default target
+/// (eg "test/invariants/fuzz/") is returned.
+fn get_target_path(default_target: String, medusa_config: Option<&Value>) -> Result<String> {
+    let Some(parsed) = medusa_config else {
+        return Ok(default_target);
+    };
+
     let target_str = parsed
         .get("compilation")
         .and_then(|comp| comp.get("platformConfig"))
         .and_then(|config| config.get("target"))
         .and_then(|val| val.as_str())
-        .unwrap_or("test/invariants/fuzz/");
+        .unwrap_or(&default_target);
 
-    // If the target json value is "" or ".", then default to "test/invariants/fuzz/"
+    // If the target json value is "" or ".", then use the default target
     let target_str = if target_str.trim().is_empty() || target_str.trim() == "." {
-        "test/invariants/fuzz/"
+        &default_target
     } else {
         target_str
     };
@@ -106,7 +794,7 @@ fn get_target_path() -> Result<String> {
                 format!("{}/", parent_str)
             }
         } else {
-            default_target
+            default_target.clone()
         }
     } else {
         // Target is already a directory, ensure it ends with '/'
@@ -120,3 +808,371 @@ fn get_target_path() -> Result<String> {
 
     Ok(final_target)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use youdusa::types::{FailedProperty, FailureKind};
+
+    fn project_without_sources(profile: Profile, target_path: &str) -> ProjectContext {
+        ProjectContext {
+            foundry_config: FoundryConfig::default(),
+            sources: SourceIndex::default(),
+            target_path: target_path.to_owned(),
+            template: None,
+            input: "stdin".to_owned(),
+            standalone: false,
+            profile,
+            fork: None,
+            fork_rpc_env: DEFAULT_FORK_RPC_ENV.to_owned(),
+            emitter: EmitterOptions::default(),
+        }
+    }
+
+    fn reproducer(name: &str, contract: &str, property: &str) -> Ast {
+        Ast::FunctionDeclaration(FunctionDeclaration::new(name).with_failed_property(
+            FailedProperty {
+                contract: contract.to_owned(),
+                property: property.to_owned(),
+                kind: FailureKind::Assertion,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_to_contract_name() {
+        assert_eq!(to_contract_name("prop_foo"), "Prop_fooReproducer");
+        assert_eq!(to_contract_name("FuzzTest"), "FuzzTestReproducer");
+        assert_eq!(to_contract_name("1foo$"), "R1foo_Reproducer");
+    }
+
+    #[test]
+    fn test_group_reproducers_by_property() {
+        let reproducers = vec![
+            reproducer("test_prop_a", "FuzzTest", "prop_a"),
+            reproducer("test_prop_b", "FuzzTest", "prop_b"),
+            reproducer("test_prop_a2", "FuzzTest", "prop_a"),
+        ];
+
+        let groups = group_reproducers(&reproducers, SplitBy::Property, "ForgeReproducer");
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Prop_aReproducer");
+        assert_eq!(groups[0].1, vec![&reproducers[0], &reproducers[2]]);
+        assert_eq!(groups[1].0, "Prop_bReproducer");
+        assert_eq!(groups[1].1, vec![&reproducers[1]]);
+    }
+
+    #[test]
+    fn test_group_reproducers_by_contract() {
+        let reproducers = vec![
+            reproducer("test_prop_a", "FuzzTest", "prop_a"),
+            reproducer("test_prop_b", "OtherFuzzTest", "prop_b"),
+            reproducer("test_prop_c", "FuzzTest", "prop_c"),
+        ];
+
+        let groups = group_reproducers(&reproducers, SplitBy::Contract, "ForgeReproducer");
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "FuzzTestReproducer");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "OtherFuzzTestReproducer");
+    }
+
+    #[test]
+    fn test_group_reproducers_none() {
+        let groups = group_reproducers(&[], SplitBy::None, "ForgeReproducer");
+        assert_eq!(groups, vec![("ForgeReproducer".to_owned(), Vec::new())]);
+
+        let groups = group_reproducers(&[], SplitBy::Property, "ForgeReproducer");
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn test_imports_default_when_sources_not_found() {
+        let project = project_without_sources(Profile::Foundry, "test/invariants/fuzz/");
+
+        assert_eq!(
+            render_imports(&project.imports(&["FuzzTest".to_owned()])),
+            "import {FuzzTest} from './FuzzTest.t.sol';\nimport {vm} from './Setup.t.sol';"
+        );
+    }
+
+    #[test]
+    fn test_imports_chimera() {
+        let project = project_without_sources(Profile::Chimera, "test/recon/");
+
+        assert_eq!(
+            render_imports(&project.imports(&project.harnesses(&[]))),
+            "import {Test} from 'forge-std/Test.sol';\n\
+             import {TargetFunctions} from './TargetFunctions.sol';\n\
+             import {FoundryAsserts} from '@chimera/FoundryAsserts.sol';"
+        );
+    }
+
+    #[test]
+    fn test_get_fork_config() {
+        let medusa_config: Value = serde_json::from_str(
+            r#"{"fuzzing": {"chainConfig": {"forkConfig": {
+                "forkModeEnabled": true, "rpcUrl": "https://rpc.example", "rpcBlock": 19000000
+            }}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_fork_config(&medusa_config),
+            Some(ForkConfig {
+                block: Some(19_000_000)
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_fork_config_disabled() {
+        let medusa_config: Value = serde_json::from_str(
+            r#"{"fuzzing": {"chainConfig": {"forkConfig": {
+                "forkModeEnabled": false, "rpcUrl": "https://rpc.example", "rpcBlock": 1
+            }}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(get_fork_config(&medusa_config), None);
+        assert_eq!(get_fork_config(&Value::Null), None);
+    }
+
+    #[test]
+    fn test_fork_setup() {
+        let mut project = project_without_sources(Profile::Foundry, "test/invariants/fuzz/");
+        project.standalone = true;
+        assert_eq!(project.fork_setup(), "");
+
+        project.fork = Some(ForkConfig { block: Some(123) });
+        project.fork_rpc_env = "MAINNET_RPC".to_owned();
+        assert_eq!(
+            project.fork_setup(),
+            "vm.createSelectFork(vm.envString('MAINNET_RPC'), 123);"
+        );
+
+        project.fork = Some(ForkConfig { block: None });
+        assert_eq!(
+            project.fork_setup(),
+            "vm.createSelectFork(vm.envString('MAINNET_RPC'));"
+        );
+    }
+
+    #[test]
+    fn test_render_closes_after_last_reproducer() {
+        let project = project_without_sources(Profile::Foundry, "non/existing/path/");
+
+        let rendered = Contract::with_base_name(
+            b"    function test_a() public {\n    }\n\n",
+            1,
+            "ForgeReproducer",
+            &["FuzzTest".to_owned()],
+            &project,
+            &mut HashSet::new(),
+        )
+        .unwrap()
+        .render()
+        .unwrap();
+
+        assert!(rendered.ends_with("    function test_a() public {\n    }\n}"));
+    }
+
+    #[test]
+    fn test_render_inherited_without_fork() {
+        let mut project = project_without_sources(Profile::Foundry, "non/existing/path/");
+        project.fork = Some(ForkConfig { block: Some(123) });
+
+        let render = |project: &ProjectContext| {
+            Contract::with_base_name(
+                b"",
+                0,
+                "ForgeReproducer",
+                &["FuzzTest".to_owned()],
+                project,
+                &mut HashSet::new(),
+            )
+            .unwrap()
+            .render()
+            .unwrap()
+        };
+
+        // The inherited harness deploys its targets before setUp, which must not select a fork
+        let inherited = render(&project);
+        assert!(inherited.contains("contract ForgeReproducer is FuzzTest {"));
+        assert!(!inherited.contains("createSelectFork"));
+        assert!(!inherited.contains("setUp()"));
+
+        project.standalone = true;
+        let standalone = render(&project);
+        assert!(standalone.contains(
+            "    function setUp() public {\n        vm.createSelectFork(vm.envString('RPC_URL'), 123);\n        harness = new FuzzTest();\n    }"
+        ));
+    }
+
+    #[test]
+    fn test_get_actors() {
+        let medusa_config: Value = serde_json::from_str(
+            r#"{"fuzzing": {"senderAddresses": ["0x10000", "0xA0000"], "deployerAddress": "0xdeadbeef"}}"#,
+        )
+        .unwrap();
+        let aliases = BTreeMap::from([(
+            "0x00000000000000000000000000000000000a0000".to_owned(),
+            "Alice".to_owned(),
+        )]);
+
+        let actors = get_actors(Some(&medusa_config), &aliases);
+
+        assert_eq!(
+            actors,
+            BTreeMap::from([
+                (
+                    "0x0000000000000000000000000000000000010000".to_owned(),
+                    "USER1".to_owned()
+                ),
+                (
+                    "0x00000000000000000000000000000000000a0000".to_owned(),
+                    "Alice".to_owned()
+                ),
+                (
+                    "0x00000000000000000000000000000000deadbeef".to_owned(),
+                    "DEPLOYER".to_owned()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_get_actors_medusa_defaults() {
+        let actors = get_actors(None, &BTreeMap::new());
+
+        assert_eq!(
+            actors.values().collect::<Vec<_>>(),
+            vec!["USER1", "USER2", "USER3"]
+        );
+    }
+
+    #[test]
+    fn test_actor_constants_and_labels() {
+        let mut project = project_without_sources(Profile::Foundry, "test/invariants/fuzz/");
+        project.emitter.actors = BTreeMap::from([
+            (
+                "0x0000000000000000000000000000000000010000".to_owned(),
+                "Alice".to_owned(),
+            ),
+            (
+                "0x0000000000000000000000000000000000020000".to_owned(),
+                "O'Brien \\ Ω".to_owned(),
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef".to_owned(),
+                "DEPLOYER".to_owned(),
+            ),
+        ]);
+
+        // Names without --actors (eg aliases for the narration) aren't declared
+        assert_eq!(project.actor_constants(), "");
+        assert_eq!(project.actor_labels(), "");
+
+        project.emitter.actor_constants = true;
+        assert_eq!(
+            project.actor_constants(),
+            "    address constant ACTOR_ALICE = 0x0000000000000000000000000000000000010000;\n    \
+             address constant ACTOR_O_BRIEN____ = 0x0000000000000000000000000000000000020000;\n    \
+             address constant ACTOR_DEPLOYER = address(uint160(0x00000000000000000000000000000000deadbeef));"
+        );
+        // The labels are string literals, whatever the alias
+        assert_eq!(
+            project.actor_labels(),
+            "        vm.label(ACTOR_ALICE, 'Alice');\n        \
+             vm.label(ACTOR_O_BRIEN____, 'O\\'Brien \\\\ \\u03a9');\n        \
+             vm.label(ACTOR_DEPLOYER, 'DEPLOYER');"
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(
+            "contract {{contract_name}} is {{ harness }} {\n{{ reproducers }}}\n",
+            minijinja::context! {
+                contract_name => "ForgeReproducer",
+                harness => "FuzzTest",
+                reproducers => "    function test_foo() public {}\n",
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "contract ForgeReproducer is FuzzTest {\n    function test_foo() public {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_template_conditionals() {
+        let template = "{% if standalone %}FuzzTest harness;{% else %}is FuzzTest{% endif %}";
+
+        assert_eq!(
+            render_template(template, minijinja::context! { standalone => true }).unwrap(),
+            "FuzzTest harness;"
+        );
+        assert_eq!(
+            render_template(template, minijinja::context! { standalone => false }).unwrap(),
+            "is FuzzTest"
+        );
+    }
+
+    #[test]
+    fn test_render_template_unknown_variable() {
+        let result = render_template(
+            "contract {{ name }} {}",
+            minijinja::context! { contract_name => "Foo" },
+        );
+
+        assert!(format!("{:#}", result.unwrap_err()).contains("undefined value"));
+    }
+
+    #[test]
+    fn test_today_format() {
+        let today = today();
+
+        assert_eq!(today.len(), 10);
+        assert!(today.starts_with("20"));
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(59), "1970-03-01");
+        assert_eq!(civil_date(10_956), "1999-12-31");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(19_783), "2024-03-01");
+        assert_eq!(civil_date(20_088), "2024-12-31");
+        assert_eq!(civil_date(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn test_find_first_unused_filename_reserved() {
+        let mut reserved_names = HashSet::new();
+        let path = "non/existing/path/".to_owned();
+
+        let first = Contract::find_first_unused_filename(
+            path.clone(),
+            "Prop_aReproducer",
+            ".t.sol",
+            &mut reserved_names,
+        )
+        .unwrap();
+        let second = Contract::find_first_unused_filename(
+            path.clone(),
+            "prop_aReproducer",
+            ".t.sol",
+            &mut reserved_names,
+        )
+        .unwrap();
+
+        assert_eq!(first, "Prop_aReproducer");
+        assert_eq!(second, "prop_aReproducer1");
+    }
+}
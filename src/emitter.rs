@@ -1,6 +1,90 @@
 use crate::ast::{Ast, FunctionCall, FunctionDeclaration, Statement};
-use anyhow::Result;
+use crate::backends::EmitterBackend;
+use crate::types::Provenance;
+use anyhow::{anyhow, Context, Result};
 use primitive_types::U256;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// The testing framework the reproducers are written for
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Profile {
+    /// Foundry, cheatcodes are called on `vm`
+    #[default]
+    Foundry,
+    /// Chimera (Recon), cheatcodes are called on `hevm`
+    Chimera,
+}
+
+impl Profile {
+    /// The name of the cheatcodes contract handle
+    pub fn cheatcodes_handle(&self) -> &'static str {
+        match self {
+            Profile::Foundry => "vm",
+            Profile::Chimera => "hevm",
+        }
+    }
+}
+
+/// The block and timestamp the harness starts at, from which the first roll/warp is offset in
+/// relative mode (Medusa's genesis, 0 and 0, by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Origin {
+    pub block: u64,
+    pub timestamp: u64,
+}
+
+/// The youdusa run generating the reproducers, for their provenance comments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunInfo {
+    /// Where the Medusa log comes from (file name or stdin)
+    pub input: String,
+
+    /// The generation date (YYYY-MM-DD)
+    pub generated_at: String,
+}
+
+/// How the reproducers are emitted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmitterOptions {
+    /// Call the properties on a deployed `harness` instead of `this` (ie the reproducer
+    /// contract deploys the harness rather than inheriting it)
+    pub standalone: bool,
+
+    pub profile: Profile,
+
+    /// Where to look for the Solidity sources, to locate the properties (empty to skip it)
+    pub source_dirs: Vec<PathBuf>,
+
+    /// Where to look for the compilation artifacts, to get the parameter names from the abi
+    pub artifacts_dir: Option<PathBuf>,
+
+    /// The version pragma of the Solidity files emitted (eg by the script backend), as pinned in
+    /// foundry.toml, `^0.8.0` if None
+    pub pragma: Option<String>,
+
+    /// Reproduce the gas limit (`{ gas: N }`) and gas price (`vm.txGasPrice`, Foundry only) of the calls
+    pub gas: bool,
+
+    /// Deal the senders, before the sequence, the ether they send along their calls
+    pub fund_senders: bool,
+
+    /// Roll and warp relative to the current block (`vm.warp(block.timestamp + N)`), the first
+    /// call being offset from this origin, instead of to Medusa's absolute block and timestamp
+    pub relative: Option<Origin>,
+
+    /// Names given to the senders (eg `Alice` or `USER1`), by full lowercased address
+    pub actors: BTreeMap<String, String>,
+
+    /// Prank the named senders by their constant (`ACTOR_ALICE`) instead of `address(0x...)`, each
+    /// reproducer labelling them first (`_labelActors()`), the reproducer contract declaring both
+    pub actor_constants: bool,
+
+    /// Comment each reproducer with where it comes from (the "[FAILED]" line, log, date, Medusa
+    /// version and how the traced call ended) and each call with its Medusa line, None to skip these comments
+    pub provenance: Option<RunInfo>,
+}
 
 /// Take an ast and create the corresponding solidity code
 pub struct Emitter {
@@ -9,6 +93,17 @@ pub struct Emitter {
 
     /// Starting indentation level
     default_indentation: usize,
+
+    options: EmitterOptions,
+
+    /// The Medusa block and timestamp reached so far in the current function, in relative mode
+    position: Origin,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Emitter {
@@ -16,77 +111,276 @@ impl Emitter {
         Self {
             output: String::new(),
             default_indentation: 4,
+            options: EmitterOptions::default(),
+            position: Origin::default(),
         }
     }
 
+    pub fn with_options(mut self, options: EmitterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Emit the solidity code from an Youdusa ast
     pub fn emit(&mut self, ast: &Ast) -> Result<()> {
         match ast {
             Ast::FunctionDeclaration(fn_declaration) => {
-                self.emit_function_declaration(fn_declaration);
+                self.emit_function_declaration(fn_declaration)
             }
             Ast::Statement(statement) => self.emit_statement(statement),
         }
-
-        Ok(())
     }
 
     pub fn get_emitted(self) -> String {
         self.output
     }
 
+    /// Emit an ast, then return (and clear) the emitted code
+    fn take_emitted(&mut self, ast: &Ast) -> Result<String> {
+        self.output.clear();
+        self.emit(ast)?;
+        Ok(std::mem::take(&mut self.output))
+    }
+
     /// Emit a function declaration
-    fn emit_function_declaration(&mut self, fn_declaration: &FunctionDeclaration) {
+    fn emit_function_declaration(&mut self, fn_declaration: &FunctionDeclaration) -> Result<()> {
+        let provenance = self
+            .options
+            .provenance
+            .clone()
+            .zip(fn_declaration.provenance());
+
+        if let Some((run, provenance)) = &provenance {
+            self.emit_provenance_header(run, provenance, fn_declaration);
+        }
+
         self.output.push_str(&" ".repeat(self.default_indentation));
 
         self.output
             .push_str(&format!("function {}() public {{\n", fn_declaration.name()));
 
+        if let Some(origin) = self.options.relative {
+            self.position = origin;
+        }
+
+        if self.options.actor_constants && !self.options.actors.is_empty() {
+            self.output
+                .push_str(&" ".repeat(self.default_indentation + 4));
+            self.output.push_str("_labelActors();\n\n");
+        }
+
+        if self.options.fund_senders {
+            self.emit_sender_funding(fn_declaration)?;
+        }
+
         // Add all the elements in the function body
         for child in fn_declaration.children() {
             match child {
-                Ast::Statement(statement) => self.emit_statement(statement),
+                Ast::Statement(statement) => self.emit_statement(statement)?,
                 Ast::FunctionDeclaration(fn_declaration) => {
-                    self.emit_function_declaration(fn_declaration)
+                    self.emit_function_declaration(fn_declaration)?
                 }
             }
+
+            if ends_group(child) {
+                self.output.push('\n');
+            }
         }
 
         self.output.push_str(&" ".repeat(self.default_indentation));
         self.output.push_str("}\n");
+
+        Ok(())
     }
 
-    fn emit_statement(&mut self, statement: &Statement) {
+    /// The NatSpec comment tracing a reproducer back to the Medusa log
+    fn emit_provenance_header(
+        &mut self,
+        run: &RunInfo,
+        provenance: &Provenance,
+        fn_declaration: &FunctionDeclaration,
+    ) {
+        let indentation = " ".repeat(self.default_indentation);
+        let mut lines = vec![format!("/// @notice {}", provenance.failed_line)];
+
+        if let Some(failed_property) = fn_declaration.failed_property() {
+            lines.push(format!("/// @dev Failure kind: {}", failed_property.kind));
+        }
+        lines.push(format!("///      Log: {}", run.input));
+        lines.push(format!("///      Date: {}", run.generated_at));
+        if let Some(medusa_version) = &provenance.medusa_version {
+            lines.push(format!("///      Medusa version: {}", medusa_version));
+        }
+        if let Some(reason) = fn_declaration
+            .trace()
+            .last()
+            .and_then(|call| call.revert_reason())
+        {
+            lines.push(format!("///      Trace outcome: {}", reason));
+        }
+
+        for line in lines {
+            self.output.push_str(&format!("{}{}\n", indentation, line));
+        }
+    }
+
+    /// Deal each sender the total value it sends during the sequence, ie the minimal balance
+    /// for none of its calls to revert for lack of funds (the pranked sender paying the value)
+    fn emit_sender_funding(&mut self, fn_declaration: &FunctionDeclaration) -> Result<()> {
+        let mut balances: Vec<(&str, U256)> = Vec::new();
+
+        for call in fn_declaration.calls() {
+            if call.cheats.value.is_zero() {
+                continue;
+            }
+
+            let sender = call.cheats.caller_to_prank.as_str();
+            match balances.iter_mut().find(|(funded, _)| *funded == sender) {
+                Some((_, balance)) => *balance = balance.saturating_add(call.cheats.value),
+                None => balances.push((sender, call.cheats.value)),
+            }
+        }
+
+        if balances.is_empty() {
+            return Ok(());
+        }
+
+        for (sender, balance) in balances {
+            self.emit_statement(&Statement::new_cheatcode_call(
+                "deal",
+                vec![format!("address({})", sender), balance.to_string()],
+            ))?;
+        }
+        self.output.push('\n');
+
+        Ok(())
+    }
+
+    fn emit_statement(&mut self, statement: &Statement) -> Result<()> {
         match statement {
             Statement::ContractCall(contract_call) => self.emit_contract_call(contract_call),
+            Statement::CheatcodeCall(cheatcode_call) => {
+                let arguments = match self.options.relative {
+                    Some(_) => self.relative_arguments(cheatcode_call)?,
+                    None => cheatcode_call.arguments.clone(),
+                };
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| self.actor_name(argument))
+                    .collect();
+
+                self.emit_contract_call(&FunctionCall {
+                    target: Some(self.options.profile.cheatcodes_handle().to_string()),
+                    arguments,
+                    ..cheatcode_call.clone()
+                })
+            }
         }
+
+        Ok(())
+    }
+
+    /// The constant naming an `address(0x...)` argument, if any, the argument itself otherwise
+    fn actor_name(&self, argument: String) -> String {
+        if !self.options.actor_constants {
+            return argument;
+        }
+
+        argument
+            .strip_prefix("address(")
+            .and_then(|address| address.strip_suffix(')'))
+            .and_then(|address| self.options.actors.get(&address.to_lowercase()))
+            .map(|name| actor_constant(name))
+            .unwrap_or(argument)
+    }
+
+    /// The arguments of a roll or warp, as an offset from the current block number/timestamp:
+    /// `block.number + 3`, the difference with the previous roll (or the origin, for the first one)
+    /// A block or timestamp which isn't a number is an error, rather than mixing absolute and relative ones
+    fn relative_arguments(&mut self, cheatcode_call: &FunctionCall) -> Result<Vec<String>> {
+        let (current, reached) = match cheatcode_call.function_name.as_str() {
+            "roll" => ("block.number", &mut self.position.block),
+            "warp" => ("block.timestamp", &mut self.position.timestamp),
+            _ => return Ok(cheatcode_call.arguments.clone()),
+        };
+
+        let argument = cheatcode_call.arguments.first().map(String::as_str);
+        let target: u64 = argument
+            .and_then(|argument| argument.parse().ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Cannot {} relatively to {:?}, not a block number or timestamp",
+                    cheatcode_call.function_name,
+                    argument.unwrap_or_default()
+                )
+            })?;
+
+        let reached = std::mem::replace(reached, target);
+
+        Ok(match target.checked_sub(reached) {
+            Some(delta) => vec![format!("{} + {}", current, delta)],
+            None => vec![format!("{} - {}", current, reached - target)],
+        })
     }
 
     /// Emit a call (used for any internal or external function call)
-    /// `target.foo{ value: X }(a, b, c);`
+    /// `target.foo{ value: X }(a, b, c);`, preceded by the Medusa line it reproduces if commented
     fn emit_contract_call(&mut self, contract_call: &FunctionCall) {
+        if let Some(medusa_line) = contract_call
+            .medusa_line
+            .as_ref()
+            .filter(|_| self.options.provenance.is_some())
+        {
+            self.output
+                .push_str(&" ".repeat(self.default_indentation + 4));
+            self.output.push_str(&format!("// {}\n", medusa_line));
+        }
+
         let mut call_to_construct = String::new();
-        let mut add_new_line = false; // only add a new line after non-cheatcodes call
 
         // Indent at current block level
         call_to_construct.push_str(&" ".repeat(self.default_indentation + 4));
 
-        // If external call, add the target and new line
+        // If external call, add the target
         if let Some(to_call) = &contract_call.target {
-            call_to_construct.push_str(to_call.as_str());
-            call_to_construct.push('.');
-            if to_call != "vm" {
-                add_new_line = true;
+            // In standalone mode, the properties are called on the deployed harness
+            if self.options.standalone && to_call == "this" {
+                call_to_construct.push_str("harness");
+            } else {
+                call_to_construct.push_str(to_call.as_str());
             }
+            call_to_construct.push('.');
         }
 
         // Add the function call
         call_to_construct.push_str(&contract_call.function_name);
 
-        // Add a { value: X } if needed
+        // Add a { value: X, gas: Y } if needed
+        let mut call_options = Vec::new();
         if let Some(value) = &contract_call.value {
             if *value != U256::zero() {
-                call_to_construct.push_str(&format!("{{ value: {} }}", value));
+                call_options.push(format!("value: {}", value));
+            }
+        }
+        let cheats = contract_call
+            .context
+            .as_ref()
+            .filter(|_| self.options.gas)
+            .map(|context| &context.cheats);
+        if let Some(gas) = cheats.and_then(|cheats| cheats.gas) {
+            call_options.push(format!("gas: {}", gas));
+        }
+        if !call_options.is_empty() {
+            call_to_construct.push_str(&format!("{{ {} }}", call_options.join(", ")));
+        }
+
+        // The gas price is set for the whole transaction, before the call (no hevm equivalent)
+        if let Some(gas_price) = cheats.and_then(|cheats| cheats.gas_price) {
+            if self.options.profile == Profile::Foundry {
+                self.output
+                    .push_str(&" ".repeat(self.default_indentation + 4));
+                self.output
+                    .push_str(&format!("vm.txGasPrice({});\n", gas_price));
             }
         }
 
@@ -95,29 +389,100 @@ impl Emitter {
         call_to_construct.push_str(&contract_call.arguments.join(", "));
         call_to_construct.push_str(");\n");
 
-        // New line if it was an external call
-        if add_new_line {
-            call_to_construct.push('\n');
+        self.output.push_str(&call_to_construct);
+    }
+}
+
+/// The constant naming an actor, prefixed not to clash with the harness': "Alice" is `ACTOR_ALICE`
+pub fn actor_constant(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("ACTOR_{}", name)
+}
+
+/// Whether a blank line follows a statement: the external calls end a group (with the cheatcodes
+/// preceding them), and so does the stopPrank closing the calls of a sender
+fn ends_group(ast: &Ast) -> bool {
+    match ast {
+        Ast::Statement(Statement::ContractCall(call)) => call.target.is_some(),
+        Ast::Statement(Statement::CheatcodeCall(call)) => call.function_name == "stopPrank",
+        Ast::FunctionDeclaration(_) => false,
+    }
+}
+
+/// The Solidity emitter is the default backend, one reproducer function per ast
+impl EmitterBackend for Emitter {
+    fn emit_reproducers(&mut self, reproducers: &[Ast], writer: &mut dyn Write) -> Result<()> {
+        for ast in reproducers {
+            let emitted = self
+                .take_emitted(ast)
+                .context("Error: Failed to create solidity function")?;
+            writeln!(writer, "{}", emitted).context("Error: Failed to write reproducer")?;
         }
 
-        self.output.push_str(&call_to_construct);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{CallContext, CheatsData};
 
     #[test]
     fn test_emit_function_declaration() {
         let mut emitter = Emitter::new();
         let test_function = FunctionDeclaration::new("test");
 
-        emitter.emit_function_declaration(&test_function);
+        emitter.emit_function_declaration(&test_function).unwrap();
 
         assert_eq!(emitter.output, "    function test() public {\n    }\n")
     }
 
+    #[test]
+    fn test_emit_function_declaration_groups() {
+        let mut emitter = Emitter::new();
+        let mut test_function = FunctionDeclaration::new("test");
+        let call = |name: &str| {
+            Ast::Statement(Statement::new_contract_call(
+                Some("this".to_string()),
+                name.to_string(),
+                None,
+                Vec::new(),
+                None,
+            ))
+        };
+        test_function.add_child(Ast::Statement(Statement::new_cheatcode_call(
+            "startPrank",
+            vec!["alice".to_string()],
+        )));
+        test_function.add_child(call("a"));
+        test_function.add_child(call("b"));
+        test_function.add_child(Ast::Statement(Statement::new_cheatcode_call(
+            "stopPrank",
+            Vec::new(),
+        )));
+        test_function.add_child(Ast::Statement(Statement::new_prank("bob")));
+        test_function.add_child(call("c"));
+
+        emitter.emit_function_declaration(&test_function).unwrap();
+
+        assert_eq!(
+            emitter.output,
+            "    function test() public {\n        vm.startPrank(alice);\n        this.a();\n\n        \
+             this.b();\n\n        vm.stopPrank();\n\n        vm.prank(bob);\n        this.c();\n\n    }\n"
+        )
+    }
+
     #[test]
     fn test_emit_contract_call_external_call() {
         let mut emitter = Emitter::new();
@@ -126,6 +491,8 @@ mod tests {
             function_name: "TestName".to_string(),
             value: Some(U256::from_dec_str("123").unwrap()),
             arguments: vec!["1,2,3".to_string()],
+            context: None,
+            medusa_line: None,
         };
 
         let default_indentation = " ".repeat(4);
@@ -136,7 +503,7 @@ mod tests {
             emitter.output,
             format!(
                 "{}{}",
-                default_indentation, "    target.TestName{ value: 123 }(1,2,3);\n\n"
+                default_indentation, "    target.TestName{ value: 123 }(1,2,3);\n"
             )
         );
     }
@@ -149,6 +516,8 @@ mod tests {
             function_name: "TestName".to_string(),
             value: Some(U256::zero()),
             arguments: vec!["1,2,3".to_string()],
+            context: None,
+            medusa_line: None,
         };
 
         let default_indentation = " ".repeat(4);
@@ -157,13 +526,266 @@ mod tests {
 
         assert_eq!(
             emitter.output,
-            format!(
-                "{}{}",
-                default_indentation, "    target.TestName(1,2,3);\n\n"
-            )
+            format!("{}{}", default_indentation, "    target.TestName(1,2,3);\n")
+        );
+    }
+
+    #[test]
+    fn test_emit_contract_call_standalone() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            standalone: true,
+            ..Default::default()
+        });
+        let test_function = FunctionCall {
+            target: Some("this".to_string()),
+            function_name: "prop_foo".to_string(),
+            value: Some(U256::zero()),
+            arguments: vec!["1".to_string()],
+            context: None,
+            medusa_line: None,
+        };
+
+        emitter.emit_contract_call(&test_function);
+
+        assert_eq!(emitter.output, "        harness.prop_foo(1);\n");
+    }
+
+    #[test]
+    fn test_emit_contract_call_gas() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            gas: true,
+            ..Default::default()
+        });
+        let test_function = FunctionCall {
+            target: Some("this".to_string()),
+            function_name: "prop_foo".to_string(),
+            value: Some(U256::from(10)),
+            arguments: vec!["1".to_string()],
+            context: Some(Box::new(CallContext {
+                contract: "FuzzTest".to_string(),
+                function: "prop_foo".to_string(),
+                signature: Some("prop_foo(uint256)".to_string()),
+                arguments: Vec::new(),
+                raw_arguments: "1".to_string(),
+                cheats: CheatsData {
+                    block_to_roll: 1,
+                    timestamp_to_warp_to: 1,
+                    caller_to_prank: "0x01".to_string(),
+                    value: U256::from(10),
+                    gas: Some(12500000),
+                    gas_price: Some(U256::from(2)),
+                },
+            })),
+            medusa_line: None,
+        };
+
+        emitter.emit_contract_call(&test_function);
+
+        assert_eq!(
+            emitter.output,
+            "        vm.txGasPrice(2);\n        this.prop_foo{ value: 10, gas: 12500000 }(1);\n"
+        );
+    }
+
+    #[test]
+    fn test_emit_sender_funding() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            fund_senders: true,
+            ..Default::default()
+        });
+        let mut fn_declaration = FunctionDeclaration::new("test");
+        for (sender, value) in [("0x01", 10), ("0x02", 0), ("0x01", 5)] {
+            fn_declaration.add_child(Ast::Statement(Statement::new_contract_call(
+                Some("this".to_string()),
+                "prop_foo".to_string(),
+                Some(U256::from(value)),
+                Vec::new(),
+                Some(CallContext {
+                    contract: "FuzzTest".to_string(),
+                    function: "prop_foo".to_string(),
+                    signature: Some("prop_foo()".to_string()),
+                    arguments: Vec::new(),
+                    raw_arguments: String::new(),
+                    cheats: CheatsData {
+                        block_to_roll: 1,
+                        timestamp_to_warp_to: 1,
+                        caller_to_prank: sender.to_string(),
+                        value: U256::from(value),
+                        gas: None,
+                        gas_price: None,
+                    },
+                }),
+            )));
+        }
+
+        emitter.emit_sender_funding(&fn_declaration).unwrap();
+
+        assert_eq!(emitter.output, "        vm.deal(address(0x01), 15);\n\n");
+    }
+
+    #[test]
+    fn test_emit_relative_roll_and_warp() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            relative: Some(Origin {
+                block: 1,
+                timestamp: 100,
+            }),
+            ..Default::default()
+        });
+        let mut fn_declaration = FunctionDeclaration::new("test");
+        for (block, timestamp) in [(5, 160), (5, 150)] {
+            fn_declaration.add_child(Ast::Statement(Statement::new_roll(block)));
+            fn_declaration.add_child(Ast::Statement(Statement::new_warp(timestamp)));
+        }
+
+        emitter.emit_function_declaration(&fn_declaration).unwrap();
+
+        assert_eq!(
+            emitter.output,
+            "    function test() public {
+        vm.roll(block.number + 4);
+        vm.warp(block.timestamp + 60);
+        vm.roll(block.number + 0);
+        vm.warp(block.timestamp - 10);
+    }\n"
+        );
+    }
+
+    #[test]
+    fn test_emit_relative_not_a_number() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            relative: Some(Origin::default()),
+            ..Default::default()
+        });
+
+        let result = emitter.emit_statement(&Statement::new_cheatcode_call(
+            "warp",
+            vec!["block.timestamp".to_string()],
+        ));
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot warp relatively to \"block.timestamp\", not a block number or timestamp"
+        );
+    }
+
+    #[test]
+    fn test_emit_provenance() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            provenance: Some(RunInfo {
+                input: "medusa.log".to_string(),
+                generated_at: "2025-01-01".to_string(),
+            }),
+            ..Default::default()
+        });
+        let mut fn_declaration = FunctionDeclaration::new("test").with_provenance(Provenance {
+            failed_line: "⇾ [FAILED] Assertion Test: FuzzTest.prop_foo()".to_string(),
+            medusa_version: None,
+        });
+        fn_declaration.add_child(Ast::Statement(Statement::new_roll(1)));
+        fn_declaration.add_child(Ast::Statement(Statement::ContractCall(FunctionCall {
+            target: Some("this".to_string()),
+            function_name: "prop_foo".to_string(),
+            value: None,
+            arguments: Vec::new(),
+            context: None,
+            medusa_line: Some("1) FuzzTest.prop_foo()() (block=1, ...)".to_string()),
+        })));
+
+        emitter.emit_function_declaration(&fn_declaration).unwrap();
+
+        assert_eq!(
+            emitter.output,
+            "    /// @notice ⇾ [FAILED] Assertion Test: FuzzTest.prop_foo()
+    ///      Log: medusa.log
+    ///      Date: 2025-01-01
+    function test() public {
+        vm.roll(1);
+        // 1) FuzzTest.prop_foo()() (block=1, ...)
+        this.prop_foo();
+
+    }\n"
         );
     }
 
+    #[test]
+    fn test_emit_statement_actor() {
+        let mut options = EmitterOptions {
+            actor_constants: true,
+            ..Default::default()
+        };
+        options.actors.insert(
+            "0x00000000000000000000000000000000000a0000".to_string(),
+            "Alice".to_string(),
+        );
+        let mut emitter = Emitter::new().with_options(options);
+
+        emitter
+            .emit_statement(&Statement::new_prank(
+                "address(0x00000000000000000000000000000000000A0000)",
+            ))
+            .unwrap();
+        emitter
+            .emit_statement(&Statement::new_prank("address(0x01)"))
+            .unwrap();
+
+        assert_eq!(
+            emitter.output,
+            "        vm.prank(ACTOR_ALICE);\n        vm.prank(address(0x01));\n"
+        );
+    }
+
+    #[test]
+    fn test_emit_function_declaration_labels_actors() {
+        let mut options = EmitterOptions::default();
+        options.actors.insert(
+            "0x00000000000000000000000000000000000a0000".to_string(),
+            "Alice".to_string(),
+        );
+        let mut test_function = FunctionDeclaration::new("test");
+        test_function.add_child(Ast::Statement(Statement::new_prank(
+            "address(0x00000000000000000000000000000000000A0000)",
+        )));
+
+        // The names alone (eg for the narration) leave the Solidity untouched
+        let mut emitter = Emitter::new().with_options(options.clone());
+        emitter.emit_function_declaration(&test_function).unwrap();
+        assert_eq!(
+            emitter.output,
+            "    function test() public {\n        \
+             vm.prank(address(0x00000000000000000000000000000000000A0000));\n    }\n"
+        );
+
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            actor_constants: true,
+            ..options
+        });
+        emitter.emit_function_declaration(&test_function).unwrap();
+        assert_eq!(
+            emitter.output,
+            "    function test() public {\n        _labelActors();\n\n        vm.prank(ACTOR_ALICE);\n    }\n"
+        );
+    }
+
+    #[test]
+    fn test_actor_constant() {
+        assert_eq!(actor_constant("Alice"), "ACTOR_ALICE");
+        assert_eq!(actor_constant("USER1"), "ACTOR_USER1");
+        assert_eq!(actor_constant("big whale"), "ACTOR_BIG_WHALE");
+    }
+
+    #[test]
+    fn test_emit_statement_cheatcode_chimera() {
+        let mut emitter = Emitter::new().with_options(EmitterOptions {
+            profile: Profile::Chimera,
+            ..Default::default()
+        });
+
+        emitter.emit_statement(&Statement::new_warp(123)).unwrap();
+
+        assert_eq!(emitter.output, "        hevm.warp(123);\n");
+    }
+
     #[test]
     fn test_emit_contract_call_internal_call() {
         let mut emitter = Emitter::new();
@@ -172,6 +794,8 @@ mod tests {
             function_name: "TestName".to_string(),
             value: None,
             arguments: vec!["".to_string()],
+            context: None,
+            medusa_line: None,
         };
 
         let default_indentation = " ".repeat(4);
@@ -192,6 +816,8 @@ mod tests {
             function_name: "TestName".to_string(),
             value: None,
             arguments: vec!["".to_string()],
+            context: None,
+            medusa_line: None,
         };
 
         let default_indentation = " ".repeat(4);
@@ -0,0 +1,304 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use toml::Table;
+use youdusa::sources::to_slash;
+
+/// The foundry.toml settings used to generate the reproducers, for the active profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundryConfig {
+    /// The contracts directory (`src`)
+    pub src: String,
+
+    /// The test directory (`test`)
+    pub test: String,
+
+    /// The compilation artifacts directory (`out`)
+    pub out: String,
+
+    /// The compiler version (`solc_version` or `solc`), if pinned
+    pub solc_version: Option<String>,
+
+    /// The import remappings (`remappings`, then remappings.txt)
+    pub remappings: Vec<Remapping>,
+}
+
+impl Default for FoundryConfig {
+    fn default() -> Self {
+        Self {
+            src: "src".to_owned(),
+            test: "test".to_owned(),
+            out: "out".to_owned(),
+            solc_version: None,
+            remappings: Vec::new(),
+        }
+    }
+}
+
+/// An import remapping, `prefix=target` (context specific remappings are ignored)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remapping {
+    pub prefix: String,
+    pub target: String,
+}
+
+impl Remapping {
+    /// Parse a `[context:]prefix=target` remapping, None if it is context specific or malformed
+    fn parse(raw: &str) -> Option<Remapping> {
+        let (prefix, target) = raw.trim().split_once('=')?;
+
+        if prefix.contains(':') || prefix.is_empty() {
+            return None;
+        }
+
+        Some(Remapping {
+            prefix: prefix.to_owned(),
+            target: target.to_owned(),
+        })
+    }
+}
+
+impl FoundryConfig {
+    /// Load foundry.toml (and remappings.txt) from the current directory, using the profile
+    /// set in FOUNDRY_PROFILE (default profile otherwise).
+    /// If there is no foundry.toml, Foundry defaults are returned.
+    pub fn load() -> Result<FoundryConfig> {
+        let foundry_file = "foundry.toml";
+        let profile = std::env::var("FOUNDRY_PROFILE").unwrap_or_else(|_| "default".to_owned());
+
+        let mut config = if Path::new(foundry_file).exists() {
+            let contents =
+                fs::read_to_string(foundry_file).context("Failed to read foundry.toml")?;
+            FoundryConfig::from_toml_str(&contents, &profile)?
+        } else {
+            FoundryConfig::default()
+        };
+
+        if Path::new("remappings.txt").exists() {
+            let contents =
+                fs::read_to_string("remappings.txt").context("Failed to read remappings.txt")?;
+            config
+                .remappings
+                .extend(contents.lines().filter_map(Remapping::parse));
+        }
+
+        Ok(config)
+    }
+
+    /// Parse foundry.toml content, the values of `profile` overriding the default profile ones
+    fn from_toml_str(contents: &str, profile: &str) -> Result<FoundryConfig> {
+        let parsed: Table = contents.parse().context("Failed to parse foundry.toml")?;
+
+        let profiles = parsed
+            .get("profile")
+            .and_then(|profiles| profiles.as_table());
+
+        // Look for a key in the active profile first, then in the default one
+        let get = |key: &str| {
+            [profile, "default"].iter().find_map(|name| {
+                profiles
+                    .and_then(|profiles| profiles.get(*name))
+                    .and_then(|profile| profile.get(key))
+            })
+        };
+
+        let default = FoundryConfig::default();
+
+        Ok(FoundryConfig {
+            src: get("src")
+                .and_then(|src| src.as_str())
+                .map(|src| src.trim_end_matches('/').to_owned())
+                .unwrap_or(default.src),
+            test: get("test")
+                .and_then(|test| test.as_str())
+                .map(|test| test.trim_end_matches('/').to_owned())
+                .unwrap_or(default.test),
+            out: get("out")
+                .and_then(|out| out.as_str())
+                .map(|out| out.trim_end_matches('/').to_owned())
+                .unwrap_or(default.out),
+            solc_version: get("solc_version")
+                .or_else(|| get("solc"))
+                .and_then(|solc| solc.as_str())
+                .map(str::to_owned),
+            remappings: get("remappings")
+                .and_then(|remappings| remappings.as_array())
+                .map(|remappings| {
+                    remappings
+                        .iter()
+                        .filter_map(|remapping| remapping.as_str())
+                        .filter_map(Remapping::parse)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The version pragma to use, based on the pinned compiler version.
+    /// Defaults to ^0.8.0 if no version is pinned (or if solc points to a binary)
+    pub fn pragma(&self) -> String {
+        match &self.solc_version {
+            Some(version)
+                if !version.is_empty()
+                    && version.chars().all(|c| c.is_ascii_digit() || c == '.') =>
+            {
+                format!("^{}", version)
+            }
+            _ => "^0.8.0".to_owned(),
+        }
+    }
+
+    /// The path to use to import `file` from a contract located in `from_dir`.
+    /// If a remapping targets the file, the remapped path is used (the most specific one),
+    /// otherwise, a path relative to from_dir.
+    pub fn import_path(&self, file: &Path, from_dir: &Path) -> String {
+        let file = normalize(file);
+
+        let remapped = self
+            .remappings
+            .iter()
+            .filter_map(|remapping| {
+                let target = normalize(Path::new(&remapping.target));
+                file.strip_prefix(&target)
+                    .ok()
+                    .map(|rest| (target.components().count(), remapping, rest))
+            })
+            .max_by_key(|(specificity, _, _)| *specificity)
+            .map(|(_, remapping, rest)| {
+                format!(
+                    "{}/{}",
+                    remapping.prefix.trim_end_matches('/'),
+                    to_slash(rest)
+                )
+            });
+
+        remapped.unwrap_or_else(|| relative_path(&file, &normalize(from_dir)))
+    }
+}
+
+/// Drop the "." components and resolve the ".." ones, when possible
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Relative path from a directory to a file, both being relative to the same root, as
+/// used in Solidity imports (ie "./Foo.sol" or "../bar/Foo.sol")
+fn relative_path(file: &Path, from_dir: &Path) -> String {
+    let file_components: Vec<_> = file.components().collect();
+    let dir_components: Vec<_> = from_dir.components().collect();
+
+    let common = file_components
+        .iter()
+        .zip(dir_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..dir_components.len() {
+        relative.push("..");
+    }
+    for component in &file_components[common..] {
+        relative.push(component);
+    }
+
+    let relative = to_slash(&relative);
+    if relative.starts_with("..") {
+        relative
+    } else {
+        format!("./{}", relative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOUNDRY_TOML: &str = r#"
+[profile.default]
+src = "src"
+test = "test/"
+solc_version = "0.8.23"
+remappings = ["forge-std/=lib/forge-std/src/", "@test/=test/", "ctx:@foo/=lib/foo/"]
+
+[profile.ci]
+test = "tests"
+"#;
+
+    #[test]
+    fn test_from_toml_str_default_profile() {
+        let config = FoundryConfig::from_toml_str(FOUNDRY_TOML, "default").unwrap();
+
+        assert_eq!(config.test, "test");
+        assert_eq!(config.out, "out");
+        assert_eq!(config.solc_version, Some("0.8.23".to_owned()));
+        assert_eq!(config.remappings.len(), 2);
+        assert_eq!(config.pragma(), "^0.8.23");
+    }
+
+    #[test]
+    fn test_from_toml_str_profile_overrides_default() {
+        let config = FoundryConfig::from_toml_str(FOUNDRY_TOML, "ci").unwrap();
+
+        assert_eq!(config.test, "tests");
+        assert_eq!(config.solc_version, Some("0.8.23".to_owned()));
+    }
+
+    #[test]
+    fn test_pragma_default() {
+        let mut config = FoundryConfig::default();
+        assert_eq!(config.pragma(), "^0.8.0");
+
+        config.solc_version = Some("/usr/bin/solc".to_owned());
+        assert_eq!(config.pragma(), "^0.8.0");
+    }
+
+    #[test]
+    fn test_import_path_remapped() {
+        let config = FoundryConfig::from_toml_str(FOUNDRY_TOML, "default").unwrap();
+
+        assert_eq!(
+            config.import_path(
+                Path::new("test/invariants/fuzz/FuzzTest.t.sol"),
+                Path::new("test/invariants/fuzz/")
+            ),
+            "@test/invariants/fuzz/FuzzTest.t.sol"
+        );
+    }
+
+    #[test]
+    fn test_import_path_relative() {
+        let config = FoundryConfig::default();
+
+        assert_eq!(
+            config.import_path(
+                Path::new("test/invariants/fuzz/FuzzTest.t.sol"),
+                Path::new("test/invariants/fuzz/")
+            ),
+            "./FuzzTest.t.sol"
+        );
+        assert_eq!(
+            config.import_path(
+                Path::new("./test/invariants/Setup.t.sol"),
+                Path::new("test/invariants/fuzz/")
+            ),
+            "../Setup.t.sol"
+        );
+    }
+}
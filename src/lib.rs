@@ -1,38 +1,70 @@
-mod ast;
+pub mod abi;
+pub mod ast;
+pub mod backends;
 mod emitter;
+mod optimizer;
 mod parser;
 mod reader;
-mod types;
+pub mod sources;
+pub mod trace;
+pub mod types;
 
 use anyhow::Context;
 
 use std::io::{Read, Write};
 
-use crate::emitter::Emitter;
+use crate::ast::Ast;
+use crate::backends::EmitterBackend;
 use crate::reader::Reader;
 
+pub use crate::emitter::{actor_constant, Emitter, EmitterOptions, Origin, Profile, RunInfo};
+pub use crate::optimizer::optimize;
+
 /// Take a Medusa trace as input, parse it and create Foundry reproducer function for every failing properties
 ///
 /// use either by piping the medusa process, `medusa fuzz | youdusa` either from a txt file, `youdusa --file log.txt`
+/// The reproducers are emitted as parsed, one roll/warp/prank per call: the optimization pass is
+/// only applied by the command line, library users call `optimize` on the `parse_input` result
 pub fn process_input(
     input: Box<dyn Read + 'static>,
     writer: &mut impl Write,
 ) -> anyhow::Result<()> {
     // build the ast
+    let reproducers = parse_input(input)?;
+
+    // emit the ast as solidity functions
+    Emitter::new().emit_reproducers(&reproducers, writer)
+}
+
+/// Parse a Medusa trace and return one ast per failing property (empty if none failed)
+pub fn parse_input(input: Box<dyn Read + 'static>) -> anyhow::Result<Vec<Ast>> {
     let reader = Reader::new(input);
     let ast = reader.parse().context("Error: Failed to parse")?;
 
-    // emit the ast as solidity functions
-    if let Some(ast_to_emit) = ast {
-        for ast in ast_to_emit {
-            let mut emitter = Emitter::new();
-            emitter
-                .emit(&ast)
-                .context("Error: Failed to create solidity function")?;
-            writeln!(writer, "{}", emitter.get_emitted())
-                .context("Error: Failed to write reproducer")?;
-        }
-    }
-
-    Ok(())
+    Ok(ast.unwrap_or_default())
+}
+
+/// Emit reproducer asts as solidity functions, one after the other
+/// (a shorthand for the Emitter backend)
+pub fn emit_reproducers(
+    reproducers: &[Ast],
+    options: &EmitterOptions,
+    writer: &mut impl Write,
+) -> anyhow::Result<()> {
+    Emitter::new()
+        .with_options(options.clone())
+        .emit_reproducers(reproducers, writer)
+}
+
+/// Emit reproducer asts using the backend registered as `format` (see backends::BACKENDS)
+pub fn emit_with_format(
+    format: &str,
+    reproducers: &[Ast],
+    options: &EmitterOptions,
+    writer: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut backend = backends::backend(format, options)
+        .ok_or_else(|| anyhow::anyhow!("Unknown output format '{}'", format))?;
+
+    backend.emit_reproducers(reproducers, writer)
 }
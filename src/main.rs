@@ -2,10 +2,20 @@ use anyhow::Context;
 use clap::{crate_authors, Parser};
 use std::fs::File;
 use std::io::{self, stdout, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use tee::TeeReader;
+use youdusa::ast::Ast;
+use youdusa::sources::SourceIndex;
+use youdusa::{EmitterOptions, Origin, Profile, RunInfo};
 
+mod annotations;
+mod config;
 mod contract_writer;
-use contract_writer::Contract;
+mod foundry_config;
+use config::Config;
+use contract_writer::{SplitBy, WriterOptions};
+use foundry_config::FoundryConfig;
 
 #[derive(Parser)]
 #[command(
@@ -20,6 +30,10 @@ use contract_writer::Contract;
           medusa fuzz | youdusa
       • File input:
           youdusa --file trace.txt
+
+    EXIT STATUS:
+      0 if no property failed, 1 if failing properties were found (0 with --no-fail),
+      2 if youdusa itself failed (invalid input, IO error, ...)
     ",
 
     help_template = concat!(
@@ -60,14 +74,267 @@ struct Args {
         long_help = "Write the output in a reproducer contract",
     )]
     write: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = SplitBy::None,
+        requires = "write",
+        help = "Split the reproducers across multiple contracts",
+        long_help = "Write one reproducer contract per failing property (property), per contract \
+                    declaring failing properties (contract) or a single one for all (none).",
+    )]
+    split_by: SplitBy,
+
+    #[arg(
+        long,
+        requires = "write",
+        help = "Custom template for the reproducer contract",
+        long_help = "Render the reproducer contract with a custom template (Jinja2 syntax) instead of the \
+                    built-in one. Available variables: {{ contract_name }}, {{ reproducers }}, {{ harness }}, {{ imports }}, \
+                    {{ pragma }}, {{ reproducer_count }}, {{ input }}, {{ generated_at }}, {{ youdusa_version }}, {{ fork }}, {{ constants }}, {{ labels }}, {{ standalone }}, {{ chimera }} and {{ extension }}. \
+                    Defaults to the `template` entry of youdusa.toml, if any."
+    )]
+    template: Option<String>,
+
+    #[arg(
+        long,
+        requires = "write",
+        help = "Environment variable holding the RPC URL of the fork (default: RPC_URL)",
+        long_help = "When medusa.json enables forking (fuzzing.chainConfig.forkConfig), standalone and Chimera \
+                    reproducer contracts select the fork in their setUp, reading the RPC URL from this environment \
+                    variable rather than hardcoding it (inheriting ones are to be run with forge test --fork-url). \
+                    Defaults to the `fork_rpc_env` entry of youdusa.toml, or RPC_URL."
+    )]
+    fork_rpc_env: Option<String>,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        requires = "write",
+        help = "Name the senders with constants (vm.prank(ACTOR_USER1)) and label them",
+        long_help = "Declare a constant per sender and deployer of medusa.json (fuzzing.senderAddresses and \
+                    deployerAddress, or Medusa's defaults) in the written contracts: ACTOR_USER1, ACTOR_USER2, \
+                    ... and ACTOR_DEPLOYER, or ACTOR_ and their alias from youdusa.toml [aliases]. The \
+                    reproducers prank these constants, and first call _labelActors() to label them with \
+                    vm.label, so that Foundry traces show names instead of raw addresses."
+    )]
+    actors: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Comment the reproducers with where they come from in the Medusa log",
+        long_help = "Add a NatSpec header to each reproducer, with the original [FAILED] line, the failure \
+                    kind, the log file, the date and the Medusa version (if printed in the log), and comment \
+                    each call with its original Medusa line, so that reviewers can check the translation."
+    )]
+    provenance: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Deploy the harness in the reproducer instead of inheriting it",
+        long_help = "Generate standalone reproducers: the reproducer contract inherits forge-std Test, \
+                    deploys the harness in setUp() and calls the properties on it (harness.prop_x(...)), \
+                    the pranks applying to these calls. The harness must have a constructor without arguments.",
+    )]
+    standalone: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Profile::Foundry,
+        conflicts_with = "standalone",
+        help = "Testing framework the reproducers are written for",
+        long_help = "Write the reproducers for Foundry (vm cheatcodes, inheriting the harness) or for \
+                    Chimera/Recon (hevm cheatcodes, a CryticToFoundry contract inheriting TargetFunctions \
+                    and FoundryAsserts, written in test/recon/).",
+    )]
+    profile: Profile,
+
+    #[arg(
+        long,
+        default_value = youdusa::backends::default_backend_name(),
+        value_parser = clap::builder::PossibleValuesParser::new(youdusa::backends::backend_names()),
+        help = "Output format of the reproducers",
+        long_help = "Output format of the reproducers printed (the contracts written with --write are \
+                    always Solidity).",
+    )]
+    format: String,
+
+    #[arg(
+        short,
+        long,
+        help = "Write the formatted output to a file instead of stdout",
+        long_help = "Write the formatted output (see --format) to a file instead of stdout, which is \
+                    useful when piping Medusa, as its log is echoed on stdout."
+    )]
+    output: Option<String>,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Print GitHub Actions annotations for the failing properties",
+        long_help = "Print a GitHub Actions `::error` workflow command per failing property, located at the \
+                    property declaration in the sources (or at the reproducer written with --write), so \
+                    that failures show inline in the PR diff. They are printed on stderr, stdout being \
+                    left to the --format output.",
+    )]
+    github_annotations: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Exit with 0 even if failing properties were found",
+        long_help = "Exit with 0 even if failing properties were found (by default, the exit status is 1 \
+                    when there are failures, so that CI jobs fail).",
+    )]
+    no_fail: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Reproduce the gas limit and gas price of the calls",
+        long_help = "Reproduce the gas limit and gas price reported by Medusa for every call, with a \
+                    `{ gas: N }` call option and `vm.txGasPrice(...)` (Foundry profile only, hevm having \
+                    no equivalent). Useful for failures depending on gasleft() or running out of gas.",
+    )]
+    gas: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Fund the senders before the sequence, for their payable calls",
+        long_help = "Deal each sender, at the start of the reproducer, the total value it sends along its \
+                    calls (vm.deal), so that payable calls do not revert for lack of funds.",
+    )]
+    fund_senders: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Reproduce the sequences call by call, without optimization",
+        long_help = "Disable the optimization pass, which drops the roll/warp to an unchanged block or \
+                    timestamp and groups consecutive calls of a same sender in a \
+                    vm.startPrank(...)/vm.stopPrank() block. Without it, every call gets its own roll, warp \
+                    and prank (the byte-exact output of previous versions).",
+    )]
+    no_optimize: bool,
+
+    #[arg(
+        long,
+        action = clap::ArgAction::SetTrue,
+        help = "Roll and warp relative to the current block and timestamp",
+        long_help = "Emit `vm.roll(block.number + N)` and `vm.warp(block.timestamp + N)`, N being the \
+                    difference with the previous call (or with the origin, for the first one), instead of \
+                    Medusa's absolute block and timestamp. Useful when setUp starts at another timestamp or \
+                    forks a chain. Can also be enabled with `relative = true` in youdusa.toml.",
+    )]
+    relative: bool,
+
+    #[arg(
+        long,
+        help = "The block Medusa starts at, for --relative (default: youdusa.toml [origin] or 0)"
+    )]
+    origin_block: Option<u64>,
+
+    #[arg(
+        long,
+        help = "The timestamp Medusa starts at, for --relative (default: youdusa.toml [origin] or 0)"
+    )]
+    origin_timestamp: Option<u64>,
+}
+
+impl Args {
+    /// Whether an option backed by youdusa.toml is used: the contract writing (template and fork RPC),
+    /// the relative mode (origin), or the sender names (aliases)
+    fn uses_config(&self) -> bool {
+        self.write || self.relative || self.actors || self.format == "narration"
+    }
+
+    /// Whether an option relies on foundry.toml: the contract writing (paths, pragma, remappings),
+    /// the formats reading the sources or artifacts (sarif, script, narration), or the GitHub annotations
+    fn uses_foundry_config(&self) -> bool {
+        self.write
+            || self.github_annotations
+            || matches!(self.format.as_str(), "sarif" | "script" | "narration")
+    }
 }
 
+/// Exit status when failing properties were found (and reproducers emitted)
+const EXIT_FAILURES_FOUND: u8 = 1;
+
+/// Exit status when youdusa itself failed (parsing, IO, ...)
+const EXIT_ERROR: u8 = 2;
+
 /// Take a Medusa trace as input, parse it and create Foundry reproducer function for every failing properties
 ///
 /// use either by piping the medusa process, `medusa fuzz | youdusa` either from a txt file, `youdusa --file log.txt`
-fn main() -> anyhow::Result<()> {
-    let stdin = io::stdin();
+fn main() -> ExitCode {
     let args = Args::parse();
+    let no_fail = args.no_fail;
+
+    match run(args) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(_) if no_fail => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::from(EXIT_FAILURES_FOUND),
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Parse the input and emit/write the reproducers, returning the number of failures found
+fn run(args: Args) -> anyhow::Result<usize> {
+    let stdin = io::stdin();
+    let config = match Config::load() {
+        Ok(config) => config,
+        // A broken youdusa.toml only matters to the options it provides the defaults of
+        Err(error) if !args.uses_config() => {
+            eprintln!("Warning: ignoring youdusa.toml: {:#}", error);
+            Config::default()
+        }
+        Err(error) => return Err(error.context("Failed to load youdusa.toml")),
+    };
+    let foundry_config = match FoundryConfig::load() {
+        Ok(foundry_config) => foundry_config,
+        // Like youdusa.toml, a broken foundry.toml only matters to the options relying on it
+        Err(error) if !args.uses_foundry_config() => {
+            eprintln!("Warning: ignoring foundry.toml: {:#}", error);
+            FoundryConfig::default()
+        }
+        Err(error) => return Err(error.context("Failed to load foundry.toml")),
+    };
+    // Piped input is read rather than --file (see below)
+    let input_name = match &args.file {
+        Some(file) if stdin.is_terminal() => file.clone(),
+        _ => "stdin".to_owned(),
+    };
+    let emitter_options = EmitterOptions {
+        standalone: args.standalone,
+        profile: args.profile,
+        source_dirs: vec![
+            PathBuf::from(&foundry_config.test),
+            PathBuf::from(&foundry_config.src),
+        ],
+        artifacts_dir: Some(PathBuf::from(&foundry_config.out)),
+        pragma: Some(foundry_config.pragma()),
+        gas: args.gas,
+        fund_senders: args.fund_senders,
+        relative: (args.relative || config.relative).then_some(Origin {
+            block: args.origin_block.unwrap_or(config.origin.block),
+            timestamp: args.origin_timestamp.unwrap_or(config.origin.timestamp),
+        }),
+        actors: config.aliases.clone(),
+        // The constants are declared by the written contracts only, see WriterOptions::actors
+        actor_constants: false,
+        provenance: args.provenance.then(|| RunInfo {
+            input: input_name.clone(),
+            generated_at: contract_writer::today(),
+        }),
+    };
 
     let input: Box<dyn Read + 'static> = if !stdin.is_terminal() {
         // piped input: we use a tee reader, to avoid buffering the whole stdout before flushing it
@@ -87,20 +354,69 @@ fn main() -> anyhow::Result<()> {
         }
     };
 
+    let mut reproducers = youdusa::parse_input(input).context("Youdusa failed")?;
+    if !args.no_optimize {
+        youdusa::optimize(&mut reproducers);
+    }
+
+    match &args.output {
+        Some(output) => {
+            let mut file = File::create(output).context("Failed to create output file")?;
+            youdusa::emit_with_format(&args.format, &reproducers, &emitter_options, &mut file)
+                .context("Youdusa failed")?;
+        }
+        None if args.write => {
+            let mut writer = Vec::new();
+            youdusa::emit_with_format(&args.format, &reproducers, &emitter_options, &mut writer)
+                .context("Youdusa failed")?;
+
+            println!("{}", String::from_utf8_lossy(&writer));
+        }
+        None => {
+            youdusa::emit_with_format(&args.format, &reproducers, &emitter_options, &mut stdout())
+                .context("Youdusa failed")?;
+        }
+    }
+
+    let mut written = Vec::new();
+
     if args.write {
-        let mut writer = Vec::new();
+        let options = WriterOptions {
+            split_by: args.split_by,
+            template: args.template.or(config.template),
+            input: input_name,
+            fork_rpc_env: args.fork_rpc_env.or(config.fork_rpc_env),
+            emitter: emitter_options.clone(),
+            actors: args.actors,
+            foundry_config,
+        };
 
-        youdusa::process_input(input, &mut writer).context("Youdusa failed")?;
+        written = contract_writer::write_reproducers(&reproducers, &options)
+            .context("Failed to write the reproducer contracts")?;
+    }
 
-        println!("{}", String::from_utf8_lossy(&writer));
+    if args.github_annotations {
+        let source_dirs: Vec<&Path> = emitter_options
+            .source_dirs
+            .iter()
+            .map(|dir| dir.as_path())
+            .collect();
+        let sources =
+            SourceIndex::scan(&source_dirs).context("Failed to scan the Solidity sources")?;
+        let written: Vec<&Path> = written.iter().map(|path| path.as_path()).collect();
+        let written =
+            SourceIndex::scan(&written).context("Failed to scan the reproducer contracts")?;
 
-        let file_writer = Contract::new(&writer).context("Contract init error")?;
-        file_writer
-            .write_rendered_contract()
-            .context("Write error")?;
-    } else {
-        youdusa::process_input(input, &mut stdout()).context("Youdusa failed")?;
+        // On stderr, not to corrupt the formatted output (GitHub reads the workflow commands on both)
+        for annotation in annotations::github_annotations(&reproducers, &sources, &written) {
+            eprintln!("{}", annotation);
+        }
     }
 
-    Ok(())
+    let failures = reproducers
+        .iter()
+        .filter(|ast| matches!(ast, Ast::FunctionDeclaration(_)))
+        .count();
+
+    Ok(failures)
 }
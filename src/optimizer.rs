@@ -0,0 +1,199 @@
+use crate::ast::{Ast, FunctionCall, Statement};
+
+/// Make the reproducers less noisy, without changing what they do:
+/// - drop the roll and warp to the block/timestamp we're already at
+/// - replace runs of calls pranked by the same sender by a single startPrank/stopPrank
+///
+/// @dev This is applied on the ast, so that every backend benefits from it. The command line applies
+/// it unless `--no-optimize`, the library leaves it to the caller (process_input doesn't)
+pub fn optimize(reproducers: &mut [Ast]) {
+    for ast in reproducers {
+        if let Ast::FunctionDeclaration(fn_declaration) = ast {
+            let children = fn_declaration.take_children();
+            let children = drop_unchanged_roll_and_warp(children);
+            fn_declaration.set_children(group_pranks(children));
+        }
+    }
+}
+
+/// The cheatcode called and its arguments, None if not a cheatcode call
+fn cheatcode(ast: &Ast) -> Option<&FunctionCall> {
+    match ast {
+        Ast::Statement(Statement::CheatcodeCall(call)) => Some(call),
+        _ => None,
+    }
+}
+
+fn drop_unchanged_roll_and_warp(children: Vec<Ast>) -> Vec<Ast> {
+    let mut block: Option<Vec<String>> = None;
+    let mut timestamp: Option<Vec<String>> = None;
+
+    children
+        .into_iter()
+        .filter(|child| {
+            let current = match cheatcode(child) {
+                Some(call) if call.function_name == "roll" => &mut block,
+                Some(call) if call.function_name == "warp" => &mut timestamp,
+                _ => return true,
+            };
+
+            let arguments = cheatcode(child).map(|call| call.arguments.clone());
+            if *current == arguments {
+                return false;
+            }
+
+            *current = arguments;
+            true
+        })
+        .collect()
+}
+
+/// A run of calls pranked by the same sender
+struct PrankRun {
+    sender: Vec<String>,
+
+    /// Index of the prank starting the run
+    start: usize,
+
+    /// Index following the last call of the run
+    end: usize,
+
+    calls: usize,
+}
+
+fn group_pranks(children: Vec<Ast>) -> Vec<Ast> {
+    let mut grouped: Vec<Ast> = Vec::new();
+    let mut run: Option<PrankRun> = None;
+
+    for child in children {
+        match (cheatcode(&child), &mut run) {
+            // Same sender as the current run: the prank is redundant
+            (Some(call), Some(current))
+                if call.function_name == "prank" && call.arguments == current.sender => {}
+            (Some(call), _) if call.function_name == "prank" => {
+                let sender = call.arguments.clone();
+                close_run(&mut grouped, run.take());
+
+                run = Some(PrankRun {
+                    sender,
+                    start: grouped.len(),
+                    end: grouped.len() + 1,
+                    calls: 0,
+                });
+                grouped.push(child);
+            }
+            (None, Some(current)) => {
+                grouped.push(child);
+                current.calls += 1;
+                current.end = grouped.len();
+            }
+            _ => grouped.push(child),
+        }
+    }
+    close_run(&mut grouped, run);
+
+    grouped
+}
+
+/// Turn the prank starting a run of multiple calls into a startPrank, stopped after its last call
+fn close_run(grouped: &mut Vec<Ast>, run: Option<PrankRun>) {
+    let Some(run) = run.filter(|run| run.calls > 1) else {
+        return;
+    };
+
+    grouped[run.start] = Ast::Statement(Statement::new_cheatcode_call("startPrank", run.sender));
+    grouped.insert(
+        run.end,
+        Ast::Statement(Statement::new_cheatcode_call("stopPrank", Vec::new())),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::FunctionDeclaration;
+
+    fn call(name: &str) -> Ast {
+        Ast::Statement(Statement::new_contract_call(
+            Some("this".to_string()),
+            name.to_string(),
+            None,
+            Vec::new(),
+            None,
+        ))
+    }
+
+    fn sequence(steps: &[(i32, i32, &str, &str)]) -> Vec<Ast> {
+        let mut fn_declaration = FunctionDeclaration::new("test");
+        for (block, timestamp, sender, name) in steps {
+            fn_declaration.add_child(Ast::Statement(Statement::new_roll(*block)));
+            fn_declaration.add_child(Ast::Statement(Statement::new_warp(*timestamp)));
+            fn_declaration.add_child(Ast::Statement(Statement::new_prank(sender)));
+            fn_declaration.add_child(call(name));
+        }
+
+        vec![Ast::FunctionDeclaration(fn_declaration)]
+    }
+
+    fn names(reproducers: &[Ast]) -> Vec<String> {
+        let Ast::FunctionDeclaration(fn_declaration) = &reproducers[0] else {
+            panic!("not a function declaration");
+        };
+
+        fn_declaration
+            .children()
+            .iter()
+            .map(|child| match child {
+                Ast::Statement(Statement::CheatcodeCall(call))
+                | Ast::Statement(Statement::ContractCall(call)) => {
+                    format!("{}({})", call.function_name, call.arguments.join(", "))
+                }
+                Ast::FunctionDeclaration(_) => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_optimize() {
+        let mut reproducers = sequence(&[
+            (1, 10, "alice", "a"),
+            (1, 20, "alice", "b"),
+            (2, 20, "alice", "c"),
+            (2, 20, "bob", "d"),
+            (3, 30, "alice", "e"),
+        ]);
+
+        optimize(&mut reproducers);
+
+        assert_eq!(
+            names(&reproducers),
+            vec![
+                "roll(1)",
+                "warp(10)",
+                "startPrank(alice)",
+                "a()",
+                "warp(20)",
+                "b()",
+                "roll(2)",
+                "c()",
+                "stopPrank()",
+                "prank(bob)",
+                "d()",
+                "roll(3)",
+                "warp(30)",
+                "prank(alice)",
+                "e()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optimize_nothing_to_do() {
+        let mut reproducers = sequence(&[(1, 10, "alice", "a"), (2, 20, "bob", "b")]);
+        let expected = names(&reproducers);
+
+        optimize(&mut reproducers);
+
+        assert_eq!(names(&reproducers), expected);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::ast::{Ast, FunctionDeclaration, Statement};
-use crate::types::CheatsData;
+use crate::ast::{Ast, FunctionCall, FunctionDeclaration, Statement};
+use crate::trace::TraceBuilder;
+use crate::types::{
+    CallContext, CheatsData, FailedProperty, FailureKind, Provenance, TypedArgument,
+};
 
 use anyhow::{anyhow, Context, Ok, Result};
 use primitive_types::U256;
@@ -17,14 +20,33 @@ pub struct Parser {
 
     /// All the ast already produced and finished
     reproducers: Vec<Ast>,
+
+    /// The Medusa version, if printed in the log so far
+    medusa_version: Option<String>,
+
+    /// The execution trace of the last reproducer, while its lines are being read
+    trace: Option<TraceBuilder>,
 }
 
 impl Parser {
     /// Branches out based on the line content:
     /// "FAILED" creates a ast (new property to reproduce, with correct naming),
     /// a numbered line is a new property function call (should be included as a new call)
-    /// "Execution Trace" ends the current trace (push the current ast with the finished ones)
+    /// "Execution Trace" ends the current trace (push the current ast with the finished ones),
+    /// the "=>" lines following it being its execution trace
     pub fn process_line(&mut self, line: String) -> Result<()> {
+        if let Some(trace) = &mut self.trace {
+            if TraceBuilder::is_trace_line(&line) {
+                trace.process_line(&line);
+                return Ok(());
+            }
+            self.finish_trace();
+        }
+
+        if let Some(version) = extract_medusa_version(&line) {
+            self.medusa_version = Some(version);
+        }
+
         if line.contains("FAILED") {
             self.create_new_reproducer(&line)
                 .context("failed to parse new broken property")?;
@@ -36,6 +58,7 @@ impl Parser {
         } else if line.contains("[Execution Trace]") {
             if let Some(ast) = self.current_ast_root.take() {
                 self.reproducers.push(ast);
+                self.trace = Some(TraceBuilder::default());
             }
         }
 
@@ -43,7 +66,9 @@ impl Parser {
     }
 
     /// Return all the reproducer ast already built
-    pub fn get_reproducers(self) -> Option<Vec<Ast>> {
+    pub fn get_reproducers(mut self) -> Option<Vec<Ast>> {
+        self.finish_trace();
+
         (!self.reproducers.is_empty()).then_some(self.reproducers)
     }
 
@@ -52,6 +77,19 @@ impl Parser {
             unique_function_counter: HashMap::new(),
             current_ast_root: None,
             reproducers: Vec::new(),
+            medusa_version: None,
+            trace: None,
+        }
+    }
+
+    /// Attach the execution trace being read, if any, to the last reproducer
+    fn finish_trace(&mut self) {
+        let Some(trace) = self.trace.take() else {
+            return;
+        };
+
+        if let Some(Ast::FunctionDeclaration(fn_declaration)) = self.reproducers.last_mut() {
+            fn_declaration.set_trace(trace.finish());
         }
     }
 
@@ -60,8 +98,22 @@ impl Parser {
         let name = self
             .extract_property_name(line)
             .ok_or_else(|| anyhow!("Couldn't parse property name"))?;
-        let unique_name = self.generate_unique_test_name(name);
-        self.create_new_ast(unique_name);
+        let contract = self
+            .extract_contract_name(line)
+            .ok_or_else(|| anyhow!("Couldn't parse contract name"))?;
+        let unique_name = self.generate_unique_test_name(name.clone());
+        self.create_new_ast(
+            unique_name,
+            Some(FailedProperty {
+                contract,
+                property: name,
+                kind: self.extract_failure_kind(line),
+            }),
+            Provenance {
+                failed_line: line.trim().to_string(),
+                medusa_version: self.medusa_version.clone(),
+            },
+        );
         Ok(())
     }
 
@@ -77,6 +129,32 @@ impl Parser {
             .into()
     }
 
+    /// Isolate the contract declaring the property, ie what comes before the '.' in
+    /// ⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)
+    fn extract_contract_name(&self, line: &str) -> Option<String> {
+        line.split_once('.')?
+            .0
+            .split_whitespace()
+            .last()
+            .map(str::to_string)
+    }
+
+    /// Isolate the kind of test which failed, ie what comes between '[FAILED]' and 'Test:' in
+    /// ⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)
+    fn extract_failure_kind(&self, line: &str) -> FailureKind {
+        let kind = line
+            .split_once("[FAILED]")
+            .and_then(|(_, rest)| rest.split_once("Test:"))
+            .map(|(kind, _)| kind.trim());
+
+        match kind {
+            Some("Assertion") => FailureKind::Assertion,
+            Some("Property") => FailureKind::Property,
+            Some("Optimization") => FailureKind::Optimization,
+            _ => FailureKind::Unknown,
+        }
+    }
+
     /// Add a "test" prefix and a number suffix to a property name
     /// and track the number of occurences of this property
     fn generate_unique_test_name(&mut self, name: String) -> String {
@@ -94,9 +172,17 @@ impl Parser {
     }
 
     /// Start building a new ast
-    fn create_new_ast(&mut self, name: String) {
-        let new_fn = Ast::FunctionDeclaration(FunctionDeclaration::new(&name));
-        self.current_ast_root = Some(new_fn);
+    fn create_new_ast(
+        &mut self,
+        name: String,
+        failed_property: Option<FailedProperty>,
+        provenance: Provenance,
+    ) {
+        let mut new_fn = FunctionDeclaration::new(&name).with_provenance(provenance);
+        if let Some(failed_property) = failed_property {
+            new_fn = new_fn.with_failed_property(failed_property);
+        }
+        self.current_ast_root = Some(Ast::FunctionDeclaration(new_fn));
     }
 
     /// Parse the line to extract block height, msg sender, timestamp, fn name and its arguments
@@ -109,7 +195,7 @@ impl Parser {
 
         // Parses property_canAlwaysCreateRequest{value: 0}(1, 1)
         let property_call = self
-            .generate_call_to_medusa_property(line.clone(), cheats_data.value)
+            .generate_call_to_medusa_property(line.clone(), cheats_data.clone())
             .context("failed to extract property to call")?;
 
         // Add all cheatcodes then the Medusa property to call
@@ -134,9 +220,15 @@ impl Parser {
     }
 
     /// Parse the property name and create a new external call targeting 'this'
+    /// The call keeps what Medusa reported about it (cheats data, signature and typed arguments if its
+    /// signature can be parsed) as context, and the line itself
     /// @dev For now, the args are returned as a Vec containing a single String
     /// futureproof would be parse them individually, including nested struct
-    fn generate_call_to_medusa_property(&self, line: String, value: U256) -> Result<Statement> {
+    fn generate_call_to_medusa_property(
+        &self,
+        line: String,
+        cheats_data: CheatsData,
+    ) -> Result<Statement> {
         let property_name = self
             .extract_property_name(&line)
             .ok_or_else(|| anyhow::anyhow!("Failed to extract property name"))?;
@@ -145,12 +237,68 @@ impl Parser {
             .parse_medusa_call_arguments(&line)
             .context("Failed to parse argsof property call")?;
 
-        Ok(Statement::new_contract_call(
-            Some("this".to_string()),
-            property_name,
-            Some(value),
+        let value = cheats_data.value;
+
+        // A signature we can't make sense of only costs the typed arguments (eg of the json
+        // output), not the call nor its context
+        let (signature, typed_arguments) = match self.parse_signature_and_typed_arguments(&line) {
+            Some((signature, typed_arguments)) => (Some(signature), typed_arguments),
+            None => (None, Vec::new()),
+        };
+        let context = CallContext {
+            contract: self.extract_contract_name(&line).unwrap_or_default(),
+            function: property_name.clone(),
+            signature,
+            arguments: typed_arguments,
+            raw_arguments: arguments.join(", "),
+            cheats: cheats_data,
+        };
+
+        Ok(Statement::ContractCall(FunctionCall {
+            target: Some("this".to_string()),
+            function_name: property_name,
+            value: Some(value),
             arguments,
-        ))
+            context: Some(Box::new(context)),
+            medusa_line: Some(line.trim().to_string()),
+        }))
+    }
+
+    /// Parse the signature and the arguments (paired with their type) of a call
+    /// "1) FuzzTest.prop_foo(uint256,(uint256,bytes))(1, (2, )) (block=..." returns
+    /// "prop_foo(uint256,(uint256,bytes))" and [(uint256, 1), ((uint256,bytes), (2, ))]
+    /// None if the values don't match the types (eg an unquoted string containing a parenthesis)
+    fn parse_signature_and_typed_arguments(
+        &self,
+        line: &str,
+    ) -> Option<(String, Vec<TypedArgument>)> {
+        let call = line.split_once('.')?.1;
+        let name_end = call.find('(')?;
+
+        let types = balanced_parenthesis_block(&call[name_end..])?;
+        let values = balanced_parenthesis_block(&call[name_end + types.len()..])?;
+
+        let signature = format!("{}{}", &call[..name_end], types);
+
+        let types = split_top_level(&types[1..types.len() - 1]);
+        let mut values = split_top_level(&values[1..values.len() - 1]);
+
+        // A single empty bytes argument is printed "()"
+        if values.is_empty() && types.len() == 1 {
+            values.push(String::new());
+        }
+
+        if values.len() != types.len() {
+            return None;
+        }
+
+        let typed_arguments = types
+            .into_iter()
+            .zip(values)
+            .map(|(type_, value)| TypedArgument { type_, value })
+            .collect();
+
+        Some((signature, typed_arguments))
     }
 
     /// Parse the values used in the different cheatcodes, as well as the msg.value to use
@@ -173,6 +321,10 @@ impl Parser {
                     timestamp_to_warp_to: map.get("time")?.parse().ok()?,
                     caller_to_prank: map.get("sender")?.parse().ok()?,
                     value: U256::from_dec_str(map.get("value")?).ok()?,
+                    gas: map.get("gas").and_then(|gas| gas.parse().ok()),
+                    gas_price: map
+                        .get("gasprice")
+                        .and_then(|gas_price| U256::from_dec_str(gas_price).ok()),
                 })
             })
     }
@@ -213,6 +365,119 @@ impl Parser {
     }
 }
 
+/// The Medusa version, if the line prints it (eg "medusa version v1.1.0" or "Medusa 1.1.0")
+/// ie a "medusa" line containing a x.y.z version, with or without its leading 'v'
+fn extract_medusa_version(line: &str) -> Option<String> {
+    if !line.to_lowercase().contains("medusa") {
+        return None;
+    }
+
+    line.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '.'))
+        .find(|word| {
+            let numbers: Vec<&str> = word.trim_start_matches('v').split('.').collect();
+            numbers.len() >= 3
+                && numbers
+                    .iter()
+                    .all(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(str::to_string)
+}
+
+/// Track whether the characters of a Medusa call are in a quoted string (`"a, (b)"`, with `\"` escapes),
+/// where commas and parenthesis are part of the value
+#[derive(Default)]
+struct QuoteTracker {
+    in_string: bool,
+    escaped: bool,
+}
+
+impl QuoteTracker {
+    /// Whether the next character is part of a string (its quotes included)
+    fn is_quoted(&mut self, c: char) -> bool {
+        if self.escaped {
+            self.escaped = false;
+            return true;
+        }
+
+        match c {
+            '\\' if self.in_string => {
+                self.escaped = true;
+                true
+            }
+            '"' => {
+                self.in_string = !self.in_string;
+                true
+            }
+            _ => self.in_string,
+        }
+    }
+}
+
+/// The leading "(...)" block of a string, including the parenthesis (ignoring the ones in strings)
+/// None if the string doesn't start with a parenthesis or if it is never closed
+fn balanced_parenthesis_block(input: &str) -> Option<&str> {
+    if !input.starts_with('(') {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut quotes = QuoteTracker::default();
+    for (index, c) in input.char_indices() {
+        if quotes.is_quoted(c) {
+            continue;
+        }
+
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&input[..=index]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Split a comma separated list, ignoring the commas in nested tuples and strings:
+/// `1,(2,3),"a,b",` is ["1", "(2,3)", "\"a,b\"", ""]
+pub(crate) fn split_top_level(list: &str) -> Vec<String> {
+    if list.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quotes = QuoteTracker::default();
+
+    for c in list.chars() {
+        if quotes.is_quoted(c) {
+            current.push(c);
+            continue;
+        }
+
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current.trim().to_string());
+
+    items
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,9 +493,18 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(
             parser.current_ast_root,
-            Some(Ast::FunctionDeclaration(FunctionDeclaration::new(
-                "test_prop_anyoneCanIncreaseFundInAPool"
-            )))
+            Some(Ast::FunctionDeclaration(
+                FunctionDeclaration::new("test_prop_anyoneCanIncreaseFundInAPool")
+                    .with_provenance(Provenance {
+                        failed_line: "⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)".to_string(),
+                        medusa_version: None,
+                    })
+                    .with_failed_property(FailedProperty {
+                        contract: "FuzzTest".to_string(),
+                        property: "prop_anyoneCanIncreaseFundInAPool".to_string(),
+                        kind: FailureKind::Assertion,
+                    })
+            ))
         );
     }
 
@@ -259,6 +533,45 @@ mod tests {
         assert_eq!(parser.current_ast_root, None);
     }
 
+    #[test]
+    fn test_process_line_provenance() {
+        let mut parser = Parser::new();
+        let call_line = "1) FuzzTest.prop_foo(uint256)(1) (block=1, time=2, gas=12500000, gasprice=1, value=0, sender=0x10000)";
+
+        for line in [
+            "⇾ medusa version v1.1.0",
+            "⇾ [FAILED] Assertion Test: FuzzTest.prop_foo(uint256)",
+            call_line,
+        ] {
+            parser.process_line(line.to_string()).unwrap();
+        }
+
+        let Some(Ast::FunctionDeclaration(fn_declaration)) = &parser.current_ast_root else {
+            panic!("no reproducer");
+        };
+        assert_eq!(
+            fn_declaration.provenance(),
+            Some(&Provenance {
+                failed_line: "⇾ [FAILED] Assertion Test: FuzzTest.prop_foo(uint256)".to_string(),
+                medusa_version: Some("v1.1.0".to_string()),
+            })
+        );
+        assert!(matches!(
+            fn_declaration.children().last(),
+            Some(Ast::Statement(Statement::ContractCall(FunctionCall { medusa_line: Some(line), .. }))) if line == call_line
+        ));
+    }
+
+    #[test]
+    fn test_extract_medusa_version() {
+        assert_eq!(
+            extract_medusa_version("Medusa 0.1.8 (commit abc)"),
+            Some("0.1.8".to_string())
+        );
+        assert_eq!(extract_medusa_version("⇾ Fuzzing with 10 workers"), None);
+        assert_eq!(extract_medusa_version("⇾ medusa config 1.2"), None);
+    }
+
     ///@todo assert the content
     #[test]
     fn test_process_line_add_from_sequence() {
@@ -287,9 +600,18 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(
             parser.reproducers,
-            vec![Ast::FunctionDeclaration(FunctionDeclaration::new(
-                "test_prop_anyoneCanIncreaseFundInAPool"
-            ))]
+            vec![Ast::FunctionDeclaration(
+                FunctionDeclaration::new("test_prop_anyoneCanIncreaseFundInAPool")
+                    .with_provenance(Provenance {
+                        failed_line: "⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)".to_string(),
+                        medusa_version: None,
+                    })
+                    .with_failed_property(FailedProperty {
+                        contract: "FuzzTest".to_string(),
+                        property: "prop_anyoneCanIncreaseFundInAPool".to_string(),
+                        kind: FailureKind::Assertion,
+                    })
+            )]
         );
         assert_eq!(parser.current_ast_root, None);
     }
@@ -306,9 +628,18 @@ mod tests {
 
         assert_eq!(
             parser.current_ast_root,
-            Some(Ast::FunctionDeclaration(FunctionDeclaration::new(
-                "test_prop_anyoneCanIncreaseFundInAPool"
-            )))
+            Some(Ast::FunctionDeclaration(
+                FunctionDeclaration::new("test_prop_anyoneCanIncreaseFundInAPool")
+                    .with_provenance(Provenance {
+                        failed_line: "⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)".to_string(),
+                        medusa_version: None,
+                    })
+                    .with_failed_property(FailedProperty {
+                        contract: "FuzzTest".to_string(),
+                        property: "prop_anyoneCanIncreaseFundInAPool".to_string(),
+                        kind: FailureKind::Assertion,
+                    })
+            ))
         );
     }
 
@@ -353,6 +684,17 @@ mod tests {
         assert_eq!(parser.extract_property_name(test_line), None);
     }
 
+    #[test]
+    fn test_extract_contract_name() {
+        let parser = Parser::new();
+        let test_line =
+        "⇾ [FAILED] Assertion Test: FuzzTest.prop_anyoneCanIncreaseFundInAPool(uint256,uint256)";
+        assert_eq!(
+            parser.extract_contract_name(test_line),
+            Some("FuzzTest".to_string())
+        );
+    }
+
     #[test]
     fn test_generate_unique_test_name() {
         let mut parser = Parser::new();
@@ -399,7 +741,7 @@ mod tests {
         let mut parser = Parser::new();
         let test_line = "1) FuzzTest.property_canAlwaysCreateRequest(uint256,uint256)(1, 1) (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
 
-        parser.create_new_ast("test".to_string());
+        parser.create_new_ast("test".to_string(), None, Provenance::default());
 
         let result = parser.add_new_call_to_ast(test_line.to_string());
 
@@ -411,7 +753,7 @@ mod tests {
         let mut parser = Parser::new();
         let test_line = "1) FuzzTest.property_canAlwaysCreateRequest(uint256,uint256)(1, 1) (block=, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
 
-        parser.create_new_ast("test".to_string());
+        parser.create_new_ast("test".to_string(), None, Provenance::default());
 
         let result = parser.add_new_call_to_ast(test_line.to_string());
 
@@ -426,7 +768,7 @@ mod tests {
         let mut parser = Parser::new();
         let test_line = "1) property_canAlwaysCreateRequest(uint256,uint256)(1, 1) (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
 
-        parser.create_new_ast("test".to_string());
+        parser.create_new_ast("test".to_string(), None, Provenance::default());
 
         let result = parser.add_new_call_to_ast(test_line.to_string());
 
@@ -436,7 +778,133 @@ mod tests {
         );
     }
 
-    //@todo generate_call_to_medusa_property and parse_cheats_data
+    #[test]
+    fn test_extract_failure_kind() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.extract_failure_kind("⇾ [FAILED] Assertion Test: FuzzTest.prop_foo()"),
+            FailureKind::Assertion
+        );
+        assert_eq!(
+            parser.extract_failure_kind("⇾ [FAILED] Property Test: FuzzTest.invariant_foo()"),
+            FailureKind::Property
+        );
+        assert_eq!(
+            parser.extract_failure_kind("⇾ [FAILED] FuzzTest.prop_foo()"),
+            FailureKind::Unknown
+        );
+    }
+
+    #[test]
+    fn test_parse_cheats_data() {
+        let parser = Parser::new();
+        let test_line = "1) FuzzTest.property_canAlwaysCreateRequest(uint256,uint256)(1, 1) (block=43494, time=315910, gas=12500000, gasprice=1, value=10, sender=0x0000000000000000000000000000000000060000)";
+
+        assert_eq!(
+            parser.parse_cheats_data(test_line.to_string()),
+            Some(CheatsData {
+                block_to_roll: 43494,
+                timestamp_to_warp_to: 315910,
+                caller_to_prank: "0x0000000000000000000000000000000000060000".to_string(),
+                value: U256::from(10),
+                gas: Some(12500000),
+                gas_price: Some(U256::one()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_signature_and_typed_arguments() {
+        let parser = Parser::new();
+        let test_line = "1) FuzzTest.property_canAlwaysCreateRequest(uint256,(uint256,bytes),bytes)(1, (2,), ) (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
+
+        let (signature, arguments) = parser
+            .parse_signature_and_typed_arguments(test_line)
+            .unwrap();
+
+        assert_eq!(
+            signature,
+            "property_canAlwaysCreateRequest(uint256,(uint256,bytes),bytes)"
+        );
+        assert_eq!(
+            arguments,
+            vec![
+                TypedArgument {
+                    type_: "uint256".to_string(),
+                    value: "1".to_string()
+                },
+                TypedArgument {
+                    type_: "(uint256,bytes)".to_string(),
+                    value: "(2,)".to_string()
+                },
+                TypedArgument {
+                    type_: "bytes".to_string(),
+                    value: "".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_signature_and_typed_arguments_no_args() {
+        let parser = Parser::new();
+        let test_line = "1) FuzzTest.property_foo()() (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
+
+        assert_eq!(
+            parser.parse_signature_and_typed_arguments(test_line),
+            Some(("property_foo()".to_string(), Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_parse_signature_and_typed_arguments_string_with_comma() {
+        let parser = Parser::new();
+        let test_line = "1) FuzzTest.property_foo(string,uint256)(\"a, (b\\\", c\", 2) (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
+
+        let (signature, arguments) = parser
+            .parse_signature_and_typed_arguments(test_line)
+            .unwrap();
+
+        assert_eq!(signature, "property_foo(string,uint256)");
+        assert_eq!(
+            arguments,
+            vec![
+                TypedArgument {
+                    type_: "string".to_string(),
+                    value: "\"a, (b\\\", c\"".to_string()
+                },
+                TypedArgument {
+                    type_: "uint256".to_string(),
+                    value: "2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_call_unparsable_signature() {
+        let parser = Parser::new();
+        // An unquoted string with a parenthesis: the values can't be matched with the types
+        let test_line = "1) FuzzTest.property_foo(string,uint256)(a)b, 2) (block=43494, time=315910, gas=12500000, gasprice=1, value=0, sender=0x0000000000000000000000000000000000060000)";
+        let cheats_data = parser.parse_cheats_data(test_line.to_string()).unwrap();
+
+        assert_eq!(parser.parse_signature_and_typed_arguments(test_line), None);
+
+        let Statement::ContractCall(call) = parser
+            .generate_call_to_medusa_property(test_line.to_string(), cheats_data)
+            .unwrap()
+        else {
+            panic!("not a contract call");
+        };
+
+        assert_eq!(call.function_name, "property_foo");
+        let context = call.context.unwrap();
+        assert_eq!(context.signature, None);
+        assert_eq!(context.raw_arguments, "a)b, 2");
+        assert_eq!(context.cheats.block_to_roll, 43494);
+        assert_eq!(context.format_call(), "property_foo(a)b, 2)");
+    }
 
     #[test]
     fn test_parse_medusa_call_arguments() {
@@ -0,0 +1,342 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Index of the contracts, functions and file-level constants declared in the Solidity sources of a project
+/// @dev This is a lightweight scan (comments and strings stripped, braces counted), not a Solidity parser
+#[derive(Debug, Default)]
+pub struct SourceIndex {
+    files: Vec<SourceFile>,
+}
+
+/// The declarations of a single Solidity file
+#[derive(Debug, PartialEq)]
+struct SourceFile {
+    path: PathBuf,
+
+    /// Contracts declared (including abstract ones), with their 1-based line
+    contracts: Vec<(String, usize)>,
+
+    /// File-level constants (eg `Vm constant vm = Vm(VM_ADDRESS);`)
+    constants: Vec<String>,
+
+    /// Functions declared in the contracts, as (contract, function, 1-based line)
+    functions: Vec<(String, String, usize)>,
+}
+
+/// Where something is declared
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+
+    /// 1-based line number
+    pub line: usize,
+}
+
+impl SourceIndex {
+    /// Recursively scan every .sol file in the roots, directories or single files (missing roots
+    /// are skipped)
+    /// Files are scanned in path order, so that lookups are deterministic
+    pub fn scan(roots: &[&Path]) -> Result<SourceIndex> {
+        let mut paths = Vec::new();
+        for root in roots {
+            collect_solidity_files(root, &mut paths)
+                .with_context(|| format!("Failed to list Solidity files in {}", root.display()))?;
+        }
+        paths.sort();
+        paths.dedup();
+
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                Ok(SourceFile::parse(path, &content))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SourceIndex { files })
+    }
+
+    /// The file declaring a given contract
+    pub fn find_contract(&self, name: &str) -> Option<&Path> {
+        self.files
+            .iter()
+            .find(|file| file.contracts.iter().any(|(contract, _)| contract == name))
+            .map(|file| file.path.as_path())
+    }
+
+    /// Where a function is declared, looking in the given contract first, then in any other
+    /// (the property could be inherited, eg from a Properties contract)
+    pub fn find_function(&self, contract: &str, name: &str) -> Option<SourceLocation> {
+        let find = |in_contract: bool| {
+            self.files.iter().find_map(|file| {
+                file.functions
+                    .iter()
+                    .find(|(declaring, function, _)| {
+                        function == name && (!in_contract || declaring == contract)
+                    })
+                    .map(|(_, _, line)| SourceLocation {
+                        path: file.path.clone(),
+                        line: *line,
+                    })
+            })
+        };
+
+        find(true).or_else(|| find(false))
+    }
+
+    /// The file declaring a given file-level constant (eg the `vm` cheatcodes handle)
+    pub fn find_constant(&self, name: &str) -> Option<&Path> {
+        self.files
+            .iter()
+            .find(|file| file.constants.iter().any(|constant| constant == name))
+            .map(|file| file.path.as_path())
+    }
+}
+
+impl SourceFile {
+    fn parse(path: PathBuf, content: &str) -> SourceFile {
+        let mut contracts = Vec::new();
+        let mut constants = Vec::new();
+        let mut functions = Vec::new();
+        let mut current_contract: Option<String> = None;
+        let mut depth = 0usize;
+
+        for (index, line) in strip_comments_and_strings(content).lines().enumerate() {
+            let tokens = tokenize(line);
+
+            if depth == 1 {
+                if let (Some(contract), Some(position)) = (
+                    &current_contract,
+                    tokens.iter().position(|token| token == "function"),
+                ) {
+                    if let Some(name) = tokens.get(position + 1) {
+                        functions.push((contract.clone(), name.clone(), index + 1));
+                    }
+                }
+            }
+
+            if depth == 0 {
+                let declaration = tokens.iter().position(|token| {
+                    token == "contract" || token == "interface" || token == "library"
+                });
+
+                if let Some(position) = declaration {
+                    current_contract = tokens.get(position + 1).cloned();
+                    if let (Some(name), "contract") = (&current_contract, tokens[position].as_str())
+                    {
+                        contracts.push((name.clone(), index + 1));
+                    }
+                } else if tokens.iter().any(|token| token == "constant") {
+                    if let Some(position) = tokens.iter().position(|token| token == "=") {
+                        if let Some(name) = position.checked_sub(1).and_then(|i| tokens.get(i)) {
+                            constants.push(name.clone());
+                        }
+                    }
+                }
+            }
+
+            for c in line.chars() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+        }
+
+        SourceFile {
+            path,
+            contracts,
+            constants,
+            functions,
+        }
+    }
+}
+
+/// Paths with forward slashes, as used by Solidity imports and SARIF uris, whatever the platform
+pub fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn collect_solidity_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    if dir.is_file() && dir.extension().is_some_and(|extension| extension == "sol") {
+        paths.push(dir.to_path_buf());
+    }
+
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_solidity_files(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "sol") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Split a line in identifiers, '=' and other single chars (whitespaces dropped)
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for c in line.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            current.push(c);
+            continue;
+        }
+
+        if !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Replace comments and string literals with spaces, keeping the line breaks (for line numbers)
+fn strip_comments_and_strings(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                stripped.push(' ');
+            }
+            '"' | '\'' => {
+                let mut escaped = false;
+                for inner in chars.by_ref() {
+                    if inner == '\n' {
+                        stripped.push('\n');
+                    }
+                    if inner == c && !escaped {
+                        break;
+                    }
+                    escaped = inner == '\\' && !escaped;
+                }
+                stripped.push(' ');
+            }
+            c => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETUP: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import {Vm} from 'forge-std/Vm.sol';
+
+// contract NotAContract {}
+Vm constant vm = Vm(address(uint160(uint256(keccak256('hevm cheat code')))));
+
+contract Setup {
+    uint256 constant internalConstant = 1;
+    string name = "contract Fake {";
+}
+
+abstract contract FuzzTest is Setup {
+    function prop_foo() public {}
+}
+"#;
+
+    #[test]
+    fn test_parse_contracts() {
+        let file = SourceFile::parse(PathBuf::from("Setup.t.sol"), SETUP);
+
+        assert_eq!(
+            file.contracts,
+            vec![("Setup".to_string(), 9), ("FuzzTest".to_string(), 14)]
+        );
+    }
+
+    #[test]
+    fn test_parse_file_level_constants_only() {
+        let file = SourceFile::parse(PathBuf::from("Setup.t.sol"), SETUP);
+
+        assert_eq!(file.constants, vec!["vm".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        let file = SourceFile::parse(PathBuf::from("Setup.t.sol"), SETUP);
+
+        assert_eq!(
+            file.functions,
+            vec![("FuzzTest".to_string(), "prop_foo".to_string(), 15)]
+        );
+    }
+
+    #[test]
+    fn test_find_function_inherited() {
+        let index = SourceIndex {
+            files: vec![SourceFile::parse(PathBuf::from("Setup.t.sol"), SETUP)],
+        };
+
+        assert_eq!(
+            index.find_function("CryticTester", "prop_foo"),
+            Some(SourceLocation {
+                path: PathBuf::from("Setup.t.sol"),
+                line: 15
+            })
+        );
+        assert_eq!(index.find_function("FuzzTest", "prop_bar"), None);
+    }
+
+    #[test]
+    fn test_to_slash() {
+        assert_eq!(
+            to_slash(&Path::new("test").join("invariants").join("FuzzTest.t.sol")),
+            "test/invariants/FuzzTest.t.sol"
+        );
+    }
+
+    #[test]
+    fn test_strip_comments_and_strings_keeps_lines() {
+        let content = "a /* b\nc */ d // e\nf 'g\\'h' i";
+
+        assert_eq!(strip_comments_and_strings(content), "a \n  d \nf   i");
+    }
+}
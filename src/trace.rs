@@ -0,0 +1,337 @@
+use serde::Serialize;
+
+/// A call of the Medusa "[Execution Trace]", with everything happening during it
+/// ` => [call] FuzzTest.prop_foo(uint256)(1) (addr=0x7D8C..., value=0, sender=0x10000)`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TraceCall {
+    /// The kind of call, as printed by Medusa (eg "call", "creation" or "proxy call")
+    pub kind: String,
+
+    /// The call, as printed by Medusa (eg `FuzzTest.prop_foo(uint256)(1)`)
+    pub call: String,
+
+    /// The address called, if printed
+    pub address: Option<String>,
+
+    /// The value sent, if printed
+    pub value: Option<String>,
+
+    /// The caller, if printed
+    pub sender: Option<String>,
+
+    /// The nested calls, events and console logs, in order
+    pub children: Vec<TraceEntry>,
+
+    /// How the call ended, None if not printed (eg the trace being cut)
+    pub outcome: Option<CallOutcome>,
+}
+
+/// Something happening during a call
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+pub enum TraceEntry {
+    Call(TraceCall),
+
+    /// An emitted event, as printed (eg `Transfer(0x10000, 0x20000, 3)`)
+    Event(String),
+
+    /// A console.log call, as printed (eg `console.log(string)("balance")`)
+    ConsoleLog(String),
+
+    /// Any other trace line, as printed
+    Other(String),
+}
+
+/// How a call ended
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+pub enum CallOutcome {
+    /// The returned values, as printed (empty if none)
+    Return(String),
+
+    /// The revert, panic or error, as printed (eg `revert ('not enough')` or `panic: assertion failed`)
+    Revert(String),
+}
+
+impl TraceCall {
+    /// The revert reason if the call reverted, None otherwise
+    pub fn revert_reason(&self) -> Option<&str> {
+        match &self.outcome {
+            Some(CallOutcome::Revert(reason)) => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+/// Render a call tree as indented text, one line per entry
+///
+/// [call] FuzzTest.prop_foo(uint256)(1)
+///     [event] Transfer(0x10000, 0x20000, 3)
+///     [revert] panic: assertion failed
+pub fn render(calls: &[TraceCall]) -> String {
+    let mut rendered = String::new();
+    for call in calls {
+        render_call(&mut rendered, call, 0);
+    }
+
+    rendered
+}
+
+fn render_call(rendered: &mut String, call: &TraceCall, depth: usize) {
+    let indentation = "    ".repeat(depth);
+    rendered.push_str(&format!("{}[{}] {}\n", indentation, call.kind, call.call));
+
+    for child in &call.children {
+        match child {
+            TraceEntry::Call(call) => render_call(rendered, call, depth + 1),
+            TraceEntry::Event(event) => {
+                rendered.push_str(&format!("{}    [event] {}\n", indentation, event));
+            }
+            TraceEntry::ConsoleLog(log) => {
+                rendered.push_str(&format!("{}    [console.log] {}\n", indentation, log));
+            }
+            TraceEntry::Other(line) => {
+                rendered.push_str(&format!("{}    {}\n", indentation, line));
+            }
+        }
+    }
+
+    match &call.outcome {
+        Some(CallOutcome::Return(values)) if values.is_empty() => {
+            rendered.push_str(&format!("{}    [return]\n", indentation));
+        }
+        Some(CallOutcome::Return(values)) => {
+            rendered.push_str(&format!("{}    [return] {}\n", indentation, values));
+        }
+        Some(CallOutcome::Revert(reason)) => {
+            rendered.push_str(&format!("{}    [revert] {}\n", indentation, reason));
+        }
+        None => {}
+    }
+}
+
+/// Build the call tree from the trace lines, nested by their indentation
+#[derive(Debug, Default)]
+pub(crate) struct TraceBuilder {
+    /// The top level calls already finished
+    roots: Vec<TraceCall>,
+
+    /// The calls being traced, with the indentation of their line, the innermost last
+    stack: Vec<(usize, TraceCall)>,
+}
+
+impl TraceBuilder {
+    /// Whether a line is part of an execution trace, ie starts with "=>"
+    pub(crate) fn is_trace_line(line: &str) -> bool {
+        line.trim_start().starts_with("=>")
+    }
+
+    /// Add a trace line to the tree: a line is nested in the last call less indented than itself
+    pub(crate) fn process_line(&mut self, line: &str) {
+        let indentation = line.len() - line.trim_start().len();
+        let content = line.trim_start().trim_start_matches("=>").trim();
+
+        while self
+            .stack
+            .last()
+            .is_some_and(|(call_indentation, _)| *call_indentation >= indentation)
+        {
+            self.close_call();
+        }
+
+        match parse_entry(content) {
+            ParsedLine::Call(call) => self.stack.push((indentation, call)),
+            ParsedLine::Outcome(outcome) => {
+                if let Some((_, call)) = self.stack.last_mut() {
+                    call.outcome = Some(outcome);
+                }
+            }
+            ParsedLine::Entry(entry) => {
+                if let Some((_, call)) = self.stack.last_mut() {
+                    call.children.push(entry);
+                }
+            }
+        }
+    }
+
+    /// The top level calls, every call still open being closed
+    pub(crate) fn finish(mut self) -> Vec<TraceCall> {
+        while !self.stack.is_empty() {
+            self.close_call();
+        }
+
+        self.roots
+    }
+
+    /// Close the innermost call, adding it to its parent (console.log calls being console logs)
+    fn close_call(&mut self) {
+        let Some((_, call)) = self.stack.pop() else {
+            return;
+        };
+
+        let entry = match call.call.strip_prefix("console.") {
+            Some(_) => TraceEntry::ConsoleLog(call.call),
+            None => TraceEntry::Call(call),
+        };
+
+        match (self.stack.last_mut(), entry) {
+            (Some((_, parent)), entry) => parent.children.push(entry),
+            (None, TraceEntry::Call(call)) => self.roots.push(call),
+            (None, _) => {}
+        }
+    }
+}
+
+enum ParsedLine {
+    Call(TraceCall),
+    Outcome(CallOutcome),
+    Entry(TraceEntry),
+}
+
+/// Parse a trace line, without its leading "=>": `[call] FuzzTest.prop_foo(uint256)(1) (addr=...)`,
+/// `[event] Transfer(...)`, `[return (true)]`, `[revert ('reason')]` or `[panic: assertion failed]`
+fn parse_entry(content: &str) -> ParsedLine {
+    let Some(tagged) = content.strip_prefix('[') else {
+        return ParsedLine::Entry(TraceEntry::Other(content.to_string()));
+    };
+
+    // "[return (true)]" and "[panic: assertion failed]" are tags on their own, the others are followed by their content
+    let lowercased = tagged.to_lowercase();
+    if let Some(inner) = tagged.strip_suffix(']').filter(|_| !tagged.contains("] ")) {
+        if let Some(values) = inner.strip_prefix("return") {
+            let values = values.trim();
+            let values = values
+                .strip_prefix('(')
+                .and_then(|values| values.strip_suffix(')'))
+                .unwrap_or(values);
+            return ParsedLine::Outcome(CallOutcome::Return(values.to_string()));
+        }
+
+        if ["revert", "panic", "error", "vm error", "assertion failed"]
+            .iter()
+            .any(|prefix| lowercased.starts_with(prefix))
+        {
+            return ParsedLine::Outcome(CallOutcome::Revert(inner.to_string()));
+        }
+    }
+
+    let Some((tag, rest)) = tagged.split_once(']') else {
+        return ParsedLine::Entry(TraceEntry::Other(content.to_string()));
+    };
+    let rest = rest.trim();
+
+    match tag {
+        "event" => ParsedLine::Entry(TraceEntry::Event(rest.to_string())),
+        tag if tag.starts_with("console") => {
+            ParsedLine::Entry(TraceEntry::ConsoleLog(rest.to_string()))
+        }
+        tag if tag.contains("call") || tag.contains("creation") => {
+            ParsedLine::Call(parse_call(tag, rest))
+        }
+        _ => ParsedLine::Entry(TraceEntry::Other(content.to_string())),
+    }
+}
+
+/// Parse a call, and its trailing `(addr=..., value=..., sender=...)` block if any
+fn parse_call(kind: &str, rest: &str) -> TraceCall {
+    let (call, attributes) = match rest.rfind(" (") {
+        Some(start) if rest[start..].contains('=') && rest.ends_with(')') => {
+            (&rest[..start], &rest[start + 2..rest.len() - 1])
+        }
+        _ => (rest, ""),
+    };
+
+    let attribute = |key: &str| {
+        attributes
+            .split(',')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| name.trim() == key)
+            .map(|(_, value)| value.trim().to_string())
+    };
+
+    TraceCall {
+        kind: kind.to_string(),
+        call: call.to_string(),
+        address: attribute("addr"),
+        value: attribute("value"),
+        sender: attribute("sender"),
+        children: Vec::new(),
+        outcome: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(lines: &[&str]) -> Vec<TraceCall> {
+        let mut builder = TraceBuilder::default();
+        for line in lines {
+            assert!(TraceBuilder::is_trace_line(line));
+            builder.process_line(line);
+        }
+
+        builder.finish()
+    }
+
+    #[test]
+    fn test_build_call_tree() {
+        let calls = build(&[
+            " => [call] FuzzTest.prop_foo(uint256)(3) (addr=0xA1, value=0, sender=0x10000)",
+            "\t => [call] Token.transfer(address,uint256)(0x20000, 3) (addr=0xB2, value=0, sender=0xA1)",
+            "\t\t => [event] Transfer(0xA1, 0x20000, 3)",
+            "\t\t => [return (true)]",
+            "\t => [call] console.log(string)(\"balance\") (addr=0x000000000000000000636F6e736F6c652e6c6f67, value=0, sender=0xA1)",
+            "\t\t => [return]",
+            "\t => [panic: assertion failed]",
+        ]);
+
+        assert_eq!(
+            calls,
+            vec![TraceCall {
+                kind: "call".to_string(),
+                call: "FuzzTest.prop_foo(uint256)(3)".to_string(),
+                address: Some("0xA1".to_string()),
+                value: Some("0".to_string()),
+                sender: Some("0x10000".to_string()),
+                children: vec![
+                    TraceEntry::Call(TraceCall {
+                        kind: "call".to_string(),
+                        call: "Token.transfer(address,uint256)(0x20000, 3)".to_string(),
+                        address: Some("0xB2".to_string()),
+                        value: Some("0".to_string()),
+                        sender: Some("0xA1".to_string()),
+                        children: vec![TraceEntry::Event("Transfer(0xA1, 0x20000, 3)".to_string())],
+                        outcome: Some(CallOutcome::Return("true".to_string())),
+                    }),
+                    TraceEntry::ConsoleLog("console.log(string)(\"balance\")".to_string()),
+                ],
+                outcome: Some(CallOutcome::Revert("panic: assertion failed".to_string())),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_revert_reason() {
+        let calls = build(&[
+            " => [call] FuzzTest.prop_foo()() (addr=0xA1, value=0, sender=0x10000)",
+            "\t => [revert ('not enough')]",
+        ]);
+
+        assert_eq!(calls[0].revert_reason(), Some("revert ('not enough')"));
+    }
+
+    #[test]
+    fn test_render() {
+        let calls = build(&[
+            " => [call] FuzzTest.prop_foo()() (addr=0xA1, value=0, sender=0x10000)",
+            "\t => [event] Log(\"here\")",
+            "\t => [return]",
+        ]);
+
+        assert_eq!(
+            render(&calls),
+            "[call] FuzzTest.prop_foo()()\n    [event] Log(\"here\")\n    [return]\n"
+        );
+    }
+}
@@ -1,9 +1,132 @@
 use primitive_types::U256;
+use serde::{Serialize, Serializer};
+use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CheatsData {
+    #[serde(rename = "block")]
     pub block_to_roll: i32,
+    #[serde(rename = "timestamp")]
     pub timestamp_to_warp_to: i32,
+    #[serde(rename = "sender")]
     pub caller_to_prank: String,
+    #[serde(serialize_with = "serialize_u256_decimal")]
     pub value: U256,
+    pub gas: Option<u64>,
+    #[serde(serialize_with = "serialize_optional_u256_decimal")]
+    pub gas_price: Option<U256>,
+}
+
+/// The property a reproducer is built for, as reported in the Medusa "[FAILED]" line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailedProperty {
+    /// The contract declaring the property (ie the fuzzing harness, eg FuzzTest)
+    pub contract: String,
+
+    /// The property function name, without its signature
+    pub property: String,
+
+    pub kind: FailureKind,
+}
+
+/// Where a reproducer comes from in the Medusa log, to check its translation
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Provenance {
+    /// The "[FAILED]" line, as printed
+    pub failed_line: String,
+
+    /// The Medusa version, if printed in the log before the failure
+    pub medusa_version: Option<String>,
+}
+
+/// The kind of test which failed, "⇾ [FAILED] Assertion Test: ..." being an assertion failure
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureKind {
+    Assertion,
+    Property,
+    Optimization,
+    Unknown,
+}
+
+impl fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            FailureKind::Assertion => "assertion",
+            FailureKind::Property => "property",
+            FailureKind::Optimization => "optimization",
+            FailureKind::Unknown => "unknown",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+/// A call of the Medusa sequence, as reported in the log
+/// `1) FuzzTest.prop_foo(uint256,bytes)(1, ) (block=1, time=2, gas=12500000, gasprice=1, value=0, sender=0x10000)`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CallContext {
+    /// The contract called (eg FuzzTest)
+    pub contract: String,
+
+    /// The function called, without its signature
+    pub function: String,
+
+    /// The function signature, eg `prop_foo(uint256,bytes)`, None if it couldn't be parsed (eg an
+    /// unquoted string containing a parenthesis), the arguments being then only known as written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+
+    /// The arguments, with their abi type (empty if the signature couldn't be parsed)
+    pub arguments: Vec<TypedArgument>,
+
+    /// The arguments, as written in the Solidity reproducer (eg `1,''`)
+    pub raw_arguments: String,
+
+    #[serde(flatten)]
+    pub cheats: CheatsData,
+}
+
+impl CallContext {
+    /// Human readable call, `function(arg1, arg2)`, empty bytes being shown as ""
+    /// (the arguments as written in the reproducer if the signature couldn't be parsed)
+    pub fn format_call(&self) -> String {
+        if self.signature.is_none() {
+            return format!("{}({})", self.function, self.raw_arguments);
+        }
+
+        let arguments: Vec<&str> = self
+            .arguments
+            .iter()
+            .map(|argument| match argument.value.as_str() {
+                "" => "\"\"",
+                value => value,
+            })
+            .collect();
+
+        format!("{}({})", self.function, arguments.join(", "))
+    }
+}
+
+/// A call argument, with its value as printed by Medusa (empty bytes being an empty string)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypedArgument {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: String,
+}
+
+/// U256 are serialized as decimal strings, as they would overflow most json number parsers
+fn serialize_u256_decimal<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+pub(crate) fn serialize_optional_u256_decimal<S: Serializer>(
+    value: &Option<U256>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serialize_u256_decimal(value, serializer),
+        None => serializer.serialize_none(),
+    }
 }
@@ -1,9 +1,55 @@
 // SPDX-License-Identifier: MIT
-pragma solidity ^0.8.0;
+pragma solidity {{ pragma }};
 
-import {FuzzTest} from './FuzzTest.t.sol';
-import {vm} from './Setup.t.sol';
+{{ imports }}
 
-contract {{ contract_name }} is FuzzTest {
+{% if standalone -%}
+contract {{ contract_name }} is Test {
+{%- if !constants.is_empty() %}
+{{ constants }}
+
+    function _labelActors() internal {
+{{ labels }}
+    }
+{% endif %}
+    {{ harness }} internal harness;
+
+    function setUp() public {
+{%- if !fork.is_empty() %}
+        {{ fork }}
+{%- endif %}
+        harness = new {{ harness }}();
+    }
+
+{{ reproducers }}
+}
+{%- else if chimera -%}
+contract {{ contract_name }} is Test, {{ harness }}, FoundryAsserts {
+{%- if !constants.is_empty() %}
+{{ constants }}
+
+    function _labelActors() internal {
+{{ labels }}
+    }
+{% endif %}
+    function setUp() public {
+{%- if !fork.is_empty() %}
+        {{ fork }}
+{%- endif %}
+        setup();
+    }
+
+{{ reproducers }}
+}
+{%- else -%}
+contract {{ contract_name }} is {{ harness }} {
+{%- if !constants.is_empty() %}
+{{ constants }}
+
+    function _labelActors() internal {
+{{ labels }}
+    }
+{% endif %}
 {{ reproducers }}
-}
\ No newline at end of file
+}
+{%- endif %}
@@ -182,3 +182,471 @@ fn test_long_seq() {
 
     }\n\n");
 }
+
+#[test]
+fn test_solidity_format_matches_process_input() {
+    let mut expected = Vec::new();
+    youdusa::process_input(
+        Box::new(load_test_file("test_log_multiple.txt")),
+        &mut expected,
+    )
+    .unwrap();
+
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_multiple.txt"))).unwrap();
+    let mut output = Vec::new();
+    youdusa::emit_with_format(
+        "solidity",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_json_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "json",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["failures"].as_array().unwrap().len(), 1);
+
+    let failure = &json["failures"][0];
+    assert_eq!(
+        failure["test_name"],
+        "test_prop_anyoneCanIncreaseFundInAPool"
+    );
+    assert_eq!(failure["contract"], "FuzzTest");
+    assert_eq!(failure["property"], "prop_anyoneCanIncreaseFundInAPool");
+    assert_eq!(failure["kind"], "assertion");
+    assert_eq!(failure["calls"].as_array().unwrap().len(), 3);
+
+    let call = &failure["calls"][2];
+    assert_eq!(call["function"], "prop_tryThisNow");
+    assert_eq!(
+        call["signature"],
+        "prop_tryThisNow(uint256,uint256,(uint256,bytes),(address,uint256),bytes)"
+    );
+    assert_eq!(call["block"], 34180);
+    assert_eq!(call["timestamp"], 321741);
+    assert_eq!(call["value"], "12345678901234567890");
+    assert_eq!(call["gas"], 12500000);
+    assert_eq!(call["gas_price"], "1");
+    assert_eq!(call["sender"], "0x0000000000000000000000000000000000070000");
+    assert_eq!(call["arguments"][2]["type"], "(uint256,bytes)");
+    assert_eq!(call["arguments"][2]["value"], "(123,)");
+}
+
+#[test]
+fn test_unparsable_signature_formats() {
+    let reproducers = youdusa::parse_input(Box::new(load_test_file(
+        "test_log_unparsable_signature.txt",
+    )))
+    .unwrap();
+    let emit = |format: &str| {
+        let mut output = Vec::new();
+        youdusa::emit_with_format(
+            format,
+            &reproducers,
+            &youdusa::EmitterOptions::default(),
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    // The call whose signature can't be parsed is still part of the sequence, untyped
+    let json: serde_json::Value = serde_json::from_str(&emit("json")).unwrap();
+    let calls = json["failures"][0]["calls"].as_array().unwrap();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1]["function"], "prop_foo");
+    assert_eq!(calls[1]["signature"], serde_json::Value::Null);
+    assert_eq!(calls[1]["raw_arguments"], "a)b, 2");
+    assert_eq!(calls[1]["block"], 4);
+    assert_eq!(
+        calls[1]["sender"],
+        "0x0000000000000000000000000000000000020000"
+    );
+
+    let markdown = emit("markdown");
+    assert!(markdown.contains("- **Sequence length:** 2 calls"));
+    assert!(markdown.contains("prop_foo(a)b, 2)"));
+
+    // cast can't encode it: the replay fails there rather than skipping it
+    let cast = emit("cast");
+    assert!(cast.contains(
+        "    echo 'Cannot replay FuzzTest.prop_foo(a)b, 2): its signature could not be parsed' >&2\n    return 1\n"
+    ));
+    // The script calls the harness as the Solidity reproducer does, from the raw arguments
+    let script = emit("script");
+    assert!(script.contains(
+        "        vm.startBroadcast(address(0x0000000000000000000000000000000000020000));\n        harness.prop_foo(a)b, 2);\n"
+    ));
+}
+
+#[test]
+fn test_markdown_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_multiple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "markdown",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(
+        output_str.contains("**2 failure(s)** on **1 distinct property(ies)**, 4 calls in total.")
+    );
+    assert!(output_str.contains("| 2 | `0x0000000000000000000000000000000000070000` | 34180 | 321741 | 0 | `prop_anyoneCanIncreaseFundInAPool(23, 334437, (1, 2), \"\")` |"));
+    assert!(output_str.contains("<details>\n<summary>Foundry reproducer</summary>"));
+    assert!(output_str.contains("function test_prop_anyoneCanIncreaseFundInAPool2() public {"));
+}
+
+#[test]
+fn test_junit_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_multiple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "junit",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains("<testsuites name=\"youdusa\" tests=\"1\" failures=\"1\">"));
+    assert!(output_str.contains("<testsuite name=\"FuzzTest\" tests=\"1\" failures=\"1\">"));
+    assert!(output_str
+        .contains("<testcase name=\"prop_anyoneCanIncreaseFundInAPool\" classname=\"FuzzTest\">"));
+    assert!(output_str.contains("<failure type=\"assertion\" message=\"Property prop_anyoneCanIncreaseFundInAPool failed after 2 calls\">"));
+    assert!(output_str.contains("function test_prop_anyoneCanIncreaseFundInAPool2() public {"));
+}
+
+#[test]
+fn test_sarif_format() {
+    let sources = tempfile::tempdir().unwrap();
+    std::fs::write(
+        sources.path().join("FuzzTest.t.sol"),
+        "contract FuzzTest {\n    function prop_anyoneCanIncreaseFundInAPool(uint256 a) public {}\n}\n",
+    )
+    .unwrap();
+
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "sarif",
+        &reproducers,
+        &youdusa::EmitterOptions {
+            source_dirs: vec![sources.path().to_path_buf()],
+            ..Default::default()
+        },
+        &mut output,
+    )
+    .unwrap();
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(result["ruleId"], "assertion-failure");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["region"]["startLine"],
+        2
+    );
+    let sources_dir = sources.path().file_name().unwrap().to_str().unwrap();
+    assert!(
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with(&format!("{}/FuzzTest.t.sol", sources_dir))
+    );
+}
+
+#[test]
+fn test_cast_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "cast",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.starts_with("#!/usr/bin/env bash\n"));
+    assert!(output_str
+        .contains("replay_test_prop_anyoneCanIncreaseFundInAPool() {\n    advance 10429 19960\n"));
+    assert!(
+        output_str.contains("anvil_impersonateAccount 0x0000000000000000000000000000000000070000")
+    );
+    assert!(output_str.contains("--from 0x0000000000000000000000000000000000070000 --value 12345678901234567890 \"$HARNESS\" 'prop_tryThisNow(uint256,uint256,(uint256,bytes),(address,uint256),bytes)' '13441534537036760751763869415731560796441041418' '334437' '(123,0x)' '(0x123,69)' '0x'\n"));
+    assert!(output_str.contains("    set -- test_prop_anyoneCanIncreaseFundInAPool\n"));
+}
+
+#[test]
+fn test_script_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "script",
+        &reproducers,
+        &youdusa::EmitterOptions {
+            pragma: Some("^0.8.19".to_string()),
+            ..Default::default()
+        },
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains("pragma solidity ^0.8.19;\n"));
+    assert!(output_str.contains("import {FuzzTest} from './FuzzTest.t.sol';"));
+    assert!(output_str.contains("contract ReproducerScript is Script {"));
+    assert!(output_str.contains("harness = FuzzTest(payable(vm.envAddress('HARNESS')));"));
+    assert!(output_str.contains(
+        "    function run() public {\n        test_prop_anyoneCanIncreaseFundInAPool();\n    }"
+    ));
+    assert!(output_str.contains("        // block 10429, time 19960\n        vm.startBroadcast(address(0x0000000000000000000000000000000000050000));\n        harness.prop_alloOwnerCanAlwaysChangePercentFee{ value: 123 }(15056796);\n        vm.stopBroadcast();\n"));
+}
+
+#[test]
+fn test_mermaid_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "mermaid",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains("```mermaid\nsequenceDiagram\n"));
+    assert!(output_str.contains("    actor S2 as 0x0000000000000000000000000000000000070000\n"));
+    assert!(output_str.contains("    Note over S2,FuzzTest: roll +23751 blocks, warp +301781s\n"));
+    assert!(output_str.contains(
+        "    S2->>FuzzTest: prop_tryThisNow(13441534537036760751763869415731560796441041418, 334437, (123,), (0x123, 69), \"\") value: 12345678901234567890\n"
+    ));
+}
+
+#[test]
+fn test_html_format() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_multiple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "html",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.starts_with("<!DOCTYPE html>"));
+    assert!(!output_str.contains("<link") && !output_str.contains("src=\"http"));
+    assert!(output_str.contains("<div class=\"stat\"><strong>2</strong>failure(s)</div>"));
+    assert!(output_str.contains("<tr><td>1</td><td class=\"sender\">0x0000000000000000000000000000000000050000</td><td>10429</td><td>19960</td><td>0</td>"));
+    assert!(
+        output_str.contains("<button class=\"copy\" data-target=\"reproducer-2\">Copy</button>")
+    );
+    assert!(output_str.contains(
+        "<pre id=\"reproducer-2\">    function test_prop_anyoneCanIncreaseFundInAPool2() public {"
+    ));
+}
+
+#[test]
+fn test_narration_format() {
+    let artifacts = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(artifacts.path().join("FuzzTest.t.sol")).unwrap();
+    std::fs::write(
+        artifacts.path().join("FuzzTest.t.sol").join("FuzzTest.json"),
+        r#"{"abi": [{"type": "function", "name": "prop_alloOwnerCanAlwaysChangePercentFee", "inputs": [{"name": "percentFee", "type": "uint256"}]}]}"#,
+    )
+    .unwrap();
+
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut options = youdusa::EmitterOptions {
+        artifacts_dir: Some(artifacts.path().to_path_buf()),
+        ..Default::default()
+    };
+    options.actors.insert(
+        "0x0000000000000000000000000000000000050000".to_string(),
+        "Alice".to_string(),
+    );
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format("narration", &reproducers, &options, &mut output).unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains("### test_prop_anyoneCanIncreaseFundInAPool\n\n"));
+    assert!(output_str.contains("1. At block 10429 (timestamp 19960), Alice calls `prop_alloOwnerCanAlwaysChangePercentFee(percentFee: 15056796)`, sending 123 wei.\n"));
+    assert!(output_str.contains("2. Then, 3 days and 11 hours later (23751 blocks), `0x0000000000000000000000000000000000070000` calls `prop_anyoneCanIncreaseFundInAPool("));
+    assert!(output_str.contains("3. In the same block, `0x0000000000000000000000000000000000070000` calls `prop_tryThisNow(13441534537036760751763869415731560796441041418, 334437, (123,), (0x123, 69), \"\")`, sending 12.34567890123456789 ETH.\n"));
+    assert!(output_str.contains(
+        "The property `FuzzTest.prop_anyoneCanIncreaseFundInAPool` then fails (assertion failure)."
+    ));
+}
+
+#[test]
+fn test_optimized_simple_log() {
+    let mut reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::optimize(&mut reproducers);
+    youdusa::emit_reproducers(
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert_eq!(
+        output_str,
+"    function test_prop_anyoneCanIncreaseFundInAPool() public {
+        vm.roll(10429);
+        vm.warp(19960);
+        vm.prank(address(0x0000000000000000000000000000000000050000));
+        this.prop_alloOwnerCanAlwaysChangePercentFee{ value: 123 }(15056796);
+
+        vm.roll(34180);
+        vm.warp(321741);
+        vm.startPrank(address(0x0000000000000000000000000000000000070000));
+        this.prop_anyoneCanIncreaseFundInAPool(13441534537036768485200417184756697876915712920751763869415731560796441041418, 334437,'');
+
+        this.prop_tryThisNow{ value: 12345678901234567890 }(13441534537036760751763869415731560796441041418, 334437, (123,''), (0x123, 69),'');
+
+        vm.stopPrank();
+
+    }\n\n");
+}
+
+#[test]
+fn test_relative_simple_log() {
+    let reproducers =
+        youdusa::parse_input(Box::new(load_test_file("test_log_simple.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_reproducers(
+        &reproducers,
+        &youdusa::EmitterOptions {
+            relative: Some(youdusa::Origin {
+                block: 1,
+                timestamp: 1,
+            }),
+            ..Default::default()
+        },
+        &mut output,
+    )
+    .unwrap();
+
+    let output_str = String::from_utf8(output).unwrap();
+
+    assert!(output_str.contains(
+        "        vm.roll(block.number + 10428);\n        vm.warp(block.timestamp + 19959);\n"
+    ));
+    assert!(output_str.contains(
+        "        vm.roll(block.number + 23751);\n        vm.warp(block.timestamp + 301781);\n"
+    ));
+    assert!(output_str
+        .contains("        vm.roll(block.number + 0);\n        vm.warp(block.timestamp + 0);\n"));
+}
+
+#[test]
+fn test_execution_trace() {
+    let reproducers = youdusa::parse_input(Box::new(load_test_file("test_log_trace.txt"))).unwrap();
+
+    let youdusa::ast::Ast::FunctionDeclaration(fn_declaration) = &reproducers[0] else {
+        panic!("not a reproducer");
+    };
+    let trace = fn_declaration.trace();
+
+    assert_eq!(trace.len(), 1);
+    assert_eq!(trace[0].call, "FuzzTest.prop_transfer(uint256)(3)");
+    assert_eq!(trace[0].revert_reason(), Some("panic: assertion failed"));
+    assert_eq!(trace[0].children.len(), 2);
+    assert!(matches!(
+        &trace[0].children[1],
+        youdusa::trace::TraceEntry::ConsoleLog(log) if log == "console.log(string,uint256)(\"balance\", 7)"
+    ));
+    // the trace lines are not taken for calls of the sequence
+    assert_eq!(fn_declaration.calls().count(), 2);
+}
+
+#[test]
+fn test_json_format_trace() {
+    let reproducers = youdusa::parse_input(Box::new(load_test_file("test_log_trace.txt"))).unwrap();
+    let mut output = Vec::new();
+
+    youdusa::emit_with_format(
+        "json",
+        &reproducers,
+        &youdusa::EmitterOptions::default(),
+        &mut output,
+    )
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let trace = &json["failures"][0]["trace"][0];
+
+    assert_eq!(trace["kind"], "call");
+    assert_eq!(
+        trace["sender"],
+        "0x0000000000000000000000000000000000020000"
+    );
+    assert_eq!(trace["outcome"]["type"], "revert");
+    assert_eq!(trace["children"][0]["type"], "call");
+    assert_eq!(
+        trace["children"][0]["content"]["children"][0]["content"],
+        "Transfer(0x7D8CB8F412B3ee9AC79558791333F41d2b1ccDAC, 0x0000000000000000000000000000000000010000, 3)"
+    );
+    assert_eq!(
+        trace["children"][0]["content"]["outcome"]["content"],
+        "true"
+    );
+}